@@ -6,9 +6,10 @@ pub mod errors;
 pub mod events;
 pub mod instructions;
 pub mod msg_codec;
-pub mod options;
 pub mod state;
 
+pub use lz_options as options;
+
 use errors::*;
 use events::*;
 use instructions::*;
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests {
 
-    use oft::options::{executor_lz_compose_option, executor_lz_receive_option};
+    use oft::options::{
+        executor_lz_compose_option, executor_lz_receive_option, DVNOption, ExecutorOptions,
+        LzComposeOption, LzReceiveOption, NativeDropOption, Options, OptionsBuilder, OptionsError,
+    };
+
+    const RECEIVER: [u8; 32] = [0x11; 32];
 
     #[test]
     fn test_add_executor_lz_receive_option() {
@@ -37,4 +42,166 @@ mod tests {
         let result = executor_lz_compose_option(index, 123_456_789);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_add_executor_lz_receive_option_with_value() {
+        /****
+         *  typescript code snippet:
+         *
+         *  const options = Options.newOptions()
+         *  options.addExecutorLzReceiveOption(200_000, 1_000_000)
+         *  console.log('lzReceiveOptions', arrayify(options.toHex()))
+         */
+        let expected: Vec<u8> = vec![
+            0, 3, 1, 0, 33, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 13, 64, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 15, 66, 64,
+        ];
+        let result =
+            OptionsBuilder::new().add_executor_lz_receive_option(200_000, 1_000_000).build();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_executor_native_drop_option() {
+        /****
+         *  typescript code snippet:
+         *
+         *  const options = Options.newOptions()
+         *  options.addExecutorNativeDropOption(1_000_000, '0x' + '11'.repeat(32))
+         *  console.log('nativeDropOptions', arrayify(options.toHex()))
+         */
+        let mut expected: Vec<u8> =
+            vec![0, 3, 1, 0, 49, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 15, 66, 64];
+        expected.extend_from_slice(&RECEIVER);
+        let result = OptionsBuilder::new()
+            .add_executor_native_drop_option(1_000_000, RECEIVER)
+            .build();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_executor_lz_compose_option_with_value() {
+        /****
+         *  typescript code snippet:
+         *
+         *  const options = Options.newOptions()
+         *  options.addExecutorComposeOption(1, 123_456_789, 1_000)
+         *  console.log('composeOptions', arrayify(options.toHex()))
+         */
+        let expected: Vec<u8> = vec![
+            0, 3, 1, 0, 35, 3, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 91, 205, 21, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 232,
+        ];
+        let result = OptionsBuilder::new()
+            .add_executor_lz_compose_option(1, 123_456_789, 1_000)
+            .build();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_executor_ordered_execution_option() {
+        /****
+         *  typescript code snippet:
+         *
+         *  const options = Options.newOptions()
+         *  options.addExecutorOrderedExecutionOption()
+         *  console.log('orderedOptions', arrayify(options.toHex()))
+         */
+        let expected: Vec<u8> = vec![0, 3, 1, 0, 1, 4];
+        let result = OptionsBuilder::new().add_executor_ordered_execution_option().build();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_add_dvn_pre_crime_option() {
+        /****
+         *  typescript code snippet:
+         *
+         *  const options = Options.newOptions()
+         *  options.addDVNPreCrimeOption(0)
+         *  console.log('dvnOptions', arrayify(options.toHex()))
+         */
+        let expected: Vec<u8> = vec![0, 3, 2, 0, 2, 0, 1];
+        let result = OptionsBuilder::new().add_dvn_pre_crime_option(0).build();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_decode_merges_duplicates() {
+        let options = OptionsBuilder::new()
+            .add_executor_lz_receive_option(200_000, 0)
+            .add_executor_native_drop_option(1_000, RECEIVER)
+            .add_executor_lz_compose_option(0, 50_000, 0)
+            .add_dvn_pre_crime_option(1)
+            .add_executor_lz_receive_option(100_000, 5)
+            .add_executor_native_drop_option(2_000, RECEIVER)
+            .add_executor_native_drop_option(3_000, [0x22; 32])
+            .add_executor_lz_compose_option(1, 70_000, 7)
+            .add_executor_lz_compose_option(0, 30_000, 3)
+            .add_executor_ordered_execution_option()
+            .build();
+        let expected = Options {
+            executor: ExecutorOptions {
+                lz_receive: Some(LzReceiveOption { gas: 300_000, value: 5 }),
+                native_drops: vec![
+                    NativeDropOption { amount: 3_000, receiver: RECEIVER },
+                    NativeDropOption { amount: 3_000, receiver: [0x22; 32] },
+                ],
+                lz_composes: vec![
+                    LzComposeOption { index: 0, gas: 80_000, value: 3 },
+                    LzComposeOption { index: 1, gas: 70_000, value: 7 },
+                ],
+                ordered_execution: true,
+            },
+            dvn: vec![DVNOption { dvn_idx: 1, option_type: 1, params: vec![] }],
+        };
+        let decoded = Options::decode(&options).unwrap();
+        assert_eq!(decoded, expected);
+        assert_eq!(decoded.executor.total_native_drop(), 6_000);
+
+        // the canonical encoding round trips
+        assert_eq!(Options::decode(&decoded.encode()).unwrap(), decoded);
+    }
+
+    #[test]
+    fn test_decode_legacy_options() {
+        // type 1: [type][gas uint256]
+        let mut type_1 = vec![0, 1];
+        type_1.extend_from_slice(&[0; 16]);
+        type_1.extend_from_slice(&200_000u128.to_be_bytes());
+        let decoded = Options::decode(&type_1).unwrap();
+        assert_eq!(decoded.executor.lz_receive, Some(LzReceiveOption { gas: 200_000, value: 0 }));
+
+        // type 2: [type][gas uint256][native drop uint256][receiver]
+        let mut type_2 = type_1.clone();
+        type_2[1] = 2;
+        type_2.extend_from_slice(&[0; 16]);
+        type_2.extend_from_slice(&1_000u128.to_be_bytes());
+        type_2.extend_from_slice(&RECEIVER);
+        let decoded = Options::decode(&type_2).unwrap();
+        assert_eq!(
+            decoded.executor.native_drops,
+            vec![NativeDropOption { amount: 1_000, receiver: RECEIVER }]
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_options() {
+        assert_eq!(Options::decode(&[]).unwrap(), Options::default());
+        assert_eq!(Options::decode(&[0, 4]), Err(OptionsError::InvalidType(4)));
+        assert_eq!(Options::decode(&[0, 3, 3, 0, 1, 1]), Err(OptionsError::InvalidWorkerId(3)));
+        assert_eq!(Options::decode(&[0, 3, 1, 0, 1, 5]), Err(OptionsError::InvalidOptionType(5)));
+        // truncated lzReceive gas
+        assert_eq!(Options::decode(&[0, 3, 1, 0, 3, 1, 0, 0]), Err(OptionsError::InvalidSize));
+        // ordered execution does not take params
+        assert_eq!(Options::decode(&[0, 3, 1, 0, 2, 4, 0]), Err(OptionsError::InvalidSize));
+        // option size beyond the options
+        assert_eq!(Options::decode(&[0, 3, 1, 0, 9, 4]), Err(OptionsError::InvalidSize));
+
+        let overflow = OptionsBuilder::new()
+            .add_executor_lz_receive_option(u128::MAX, 0)
+            .add_executor_lz_receive_option(1, 0)
+            .build();
+        assert_eq!(Options::decode(&overflow), Err(OptionsError::Overflow));
+    }
 }
@@ -0,0 +1,341 @@
+//! Builder and decoder of the LayerZero type-3 options, matching the `Options` class of
+//! `@layerzerolabs/lz-v2-utilities`. This crate has no dependencies, so it can be used both
+//! on-chain and off-chain.
+
+pub const TYPE_1: u16 = 1; // legacy, lzReceive gas
+pub const TYPE_2: u16 = 2; // legacy, lzReceive gas and native drop
+pub const TYPE_3: u16 = 3;
+
+pub const EXECUTOR_ID: u8 = 1; // WORKER_ID
+pub const DVN_ID: u8 = 2; // WORKER_ID
+
+pub const LZ_RECEIVE_TYPE: u8 = 1; // OPTION_TYPE
+pub const NATIVE_DROP_TYPE: u8 = 2; // OPTION_TYPE
+pub const LZ_COMPOSE_TYPE: u8 = 3; // OPTION_TYPE
+pub const ORDERED_EXECUTION_TYPE: u8 = 4; // OPTION_TYPE
+
+pub const DVN_PRECRIME_TYPE: u8 = 1; // OPTION_TYPE
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionsError {
+    InvalidType(u16),
+    InvalidWorkerId(u8),
+    InvalidOptionType(u8),
+    InvalidSize,
+    Overflow,
+}
+
+pub type OptionsResult<T> = core::result::Result<T, OptionsError>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LzReceiveOption {
+    pub gas: u128,
+    pub value: u128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NativeDropOption {
+    pub amount: u128,
+    pub receiver: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LzComposeOption {
+    pub index: u16,
+    pub gas: u128,
+    pub value: u128,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DVNOption {
+    pub dvn_idx: u8,
+    pub option_type: u8,
+    pub params: Vec<u8>,
+}
+
+/// The executor options of a message, with duplicated options merged the same way as the
+/// executor: gas and values are summed per lzReceive, native drop receiver and compose index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutorOptions {
+    pub lz_receive: Option<LzReceiveOption>,
+    pub native_drops: Vec<NativeDropOption>,
+    pub lz_composes: Vec<LzComposeOption>,
+    pub ordered_execution: bool,
+}
+
+impl ExecutorOptions {
+    pub fn lz_receive_gas(&self) -> u128 {
+        self.lz_receive.map_or(0, |lz_receive| lz_receive.gas)
+    }
+
+    pub fn max_lz_compose_gas(&self) -> u128 {
+        self.lz_composes.iter().map(|compose| compose.gas).max().unwrap_or(0)
+    }
+
+    pub fn total_native_drop(&self) -> u128 {
+        self.native_drops
+            .iter()
+            .fold(0, |total, drop| total.saturating_add(drop.amount))
+    }
+
+    fn add_lz_receive(&mut self, gas: u128, value: u128) -> OptionsResult<()> {
+        let lz_receive = self.lz_receive.get_or_insert_with(Default::default);
+        lz_receive.gas = checked_add(lz_receive.gas, gas)?;
+        lz_receive.value = checked_add(lz_receive.value, value)?;
+        Ok(())
+    }
+
+    fn add_native_drop(&mut self, amount: u128, receiver: [u8; 32]) -> OptionsResult<()> {
+        match self.native_drops.iter_mut().find(|drop| drop.receiver == receiver) {
+            Some(drop) => drop.amount = checked_add(drop.amount, amount)?,
+            None => self.native_drops.push(NativeDropOption { amount, receiver }),
+        }
+        Ok(())
+    }
+
+    fn add_lz_compose(&mut self, index: u16, gas: u128, value: u128) -> OptionsResult<()> {
+        match self.lz_composes.iter_mut().find(|compose| compose.index == index) {
+            Some(compose) => {
+                compose.gas = checked_add(compose.gas, gas)?;
+                compose.value = checked_add(compose.value, value)?;
+            },
+            None => self.lz_composes.push(LzComposeOption { index, gas, value }),
+        }
+        Ok(())
+    }
+}
+
+/// The typed form of options. `decode` merges duplicates and `encode` writes type 3 options in a
+/// canonical order, so that `Options::decode(&options.encode())` returns the same options.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub executor: ExecutorOptions,
+    pub dvn: Vec<DVNOption>,
+}
+
+impl Options {
+    /// Decodes type 1, 2 or 3 options. Empty options have none.
+    pub fn decode(options: &[u8]) -> OptionsResult<Self> {
+        let mut decoded = Self::default();
+        if options.is_empty() {
+            return Ok(decoded);
+        }
+        let executor = &mut decoded.executor;
+        let mut reader = Reader(options);
+        match reader.u16()? {
+            TYPE_1 => {
+                executor.add_lz_receive(reader.u256()?, 0)?;
+            },
+            TYPE_2 => {
+                executor.add_lz_receive(reader.u256()?, 0)?;
+                let amount = reader.u256()?;
+                executor.add_native_drop(amount, reader.bytes32()?)?;
+            },
+            TYPE_3 => {
+                while !reader.is_empty() {
+                    let worker_id = reader.u8()?;
+                    let size = reader.u16()? as usize;
+                    let mut option = Reader(reader.take(size)?);
+                    match worker_id {
+                        EXECUTOR_ID => match option.u8()? {
+                            LZ_RECEIVE_TYPE => {
+                                let gas = option.u128()?;
+                                executor.add_lz_receive(gas, option.optional_u128()?)?;
+                            },
+                            NATIVE_DROP_TYPE => {
+                                let amount = option.u128()?;
+                                executor.add_native_drop(amount, option.bytes32()?)?;
+                            },
+                            LZ_COMPOSE_TYPE => {
+                                let index = option.u16()?;
+                                let gas = option.u128()?;
+                                executor.add_lz_compose(index, gas, option.optional_u128()?)?;
+                            },
+                            ORDERED_EXECUTION_TYPE => executor.ordered_execution = true,
+                            option_type => {
+                                return Err(OptionsError::InvalidOptionType(option_type))
+                            },
+                        },
+                        DVN_ID => {
+                            let dvn_idx = option.u8()?;
+                            let option_type = option.u8()?;
+                            let params = option.take(option.0.len())?.to_vec();
+                            decoded.dvn.push(DVNOption { dvn_idx, option_type, params });
+                        },
+                        worker_id => return Err(OptionsError::InvalidWorkerId(worker_id)),
+                    }
+                    if !option.is_empty() {
+                        return Err(OptionsError::InvalidSize);
+                    }
+                }
+            },
+            options_type => return Err(OptionsError::InvalidType(options_type)),
+        }
+        if !reader.is_empty() {
+            return Err(OptionsError::InvalidSize);
+        }
+        Ok(decoded)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let executor = &self.executor;
+        let mut builder = OptionsBuilder::new();
+        if let Some(lz_receive) = executor.lz_receive {
+            builder = builder.add_executor_lz_receive_option(lz_receive.gas, lz_receive.value);
+        }
+        for drop in &executor.native_drops {
+            builder = builder.add_executor_native_drop_option(drop.amount, drop.receiver);
+        }
+        for compose in &executor.lz_composes {
+            builder =
+                builder.add_executor_lz_compose_option(compose.index, compose.gas, compose.value);
+        }
+        if executor.ordered_execution {
+            builder = builder.add_executor_ordered_execution_option();
+        }
+        for dvn in &self.dvn {
+            builder = builder.add_dvn_option(dvn.dvn_idx, dvn.option_type, &dvn.params);
+        }
+        builder.build()
+    }
+}
+
+/// Decodes the executor options of type 1, 2 or 3 options.
+pub fn decode_executor_options(options: &[u8]) -> OptionsResult<ExecutorOptions> {
+    Ok(Options::decode(options)?.executor)
+}
+
+/// Builds type 3 options byte for byte like `Options.newOptions()`, in the order the options are
+/// added and without merging duplicates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionsBuilder {
+    options: Vec<u8>,
+}
+
+impl Default for OptionsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptionsBuilder {
+    pub fn new() -> Self {
+        Self { options: TYPE_3.to_be_bytes().to_vec() }
+    }
+
+    pub fn add_executor_lz_receive_option(self, gas: u128, value: u128) -> Self {
+        let mut params = gas.to_be_bytes().to_vec();
+        if value > 0 {
+            params.extend_from_slice(&value.to_be_bytes());
+        }
+        self.add_executor_option(LZ_RECEIVE_TYPE, &params)
+    }
+
+    pub fn add_executor_native_drop_option(self, amount: u128, receiver: [u8; 32]) -> Self {
+        let mut params = amount.to_be_bytes().to_vec();
+        params.extend_from_slice(&receiver);
+        self.add_executor_option(NATIVE_DROP_TYPE, &params)
+    }
+
+    pub fn add_executor_lz_compose_option(self, index: u16, gas: u128, value: u128) -> Self {
+        let mut params = index.to_be_bytes().to_vec();
+        params.extend_from_slice(&gas.to_be_bytes());
+        if value > 0 {
+            params.extend_from_slice(&value.to_be_bytes());
+        }
+        self.add_executor_option(LZ_COMPOSE_TYPE, &params)
+    }
+
+    pub fn add_executor_ordered_execution_option(self) -> Self {
+        self.add_executor_option(ORDERED_EXECUTION_TYPE, &[])
+    }
+
+    pub fn add_dvn_pre_crime_option(self, dvn_idx: u8) -> Self {
+        self.add_dvn_option(dvn_idx, DVN_PRECRIME_TYPE, &[])
+    }
+
+    pub fn add_dvn_option(mut self, dvn_idx: u8, option_type: u8, params: &[u8]) -> Self {
+        self.options.push(DVN_ID); // worker id
+        self.options.extend_from_slice(&(params.len() as u16 + 2).to_be_bytes());
+        self.options.push(dvn_idx);
+        self.options.push(option_type);
+        self.options.extend_from_slice(params);
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.options
+    }
+
+    fn add_executor_option(mut self, option_type: u8, params: &[u8]) -> Self {
+        self.options.push(EXECUTOR_ID); // worker id
+        self.options.extend_from_slice(&(params.len() as u16 + 1).to_be_bytes());
+        self.options.push(option_type);
+        self.options.extend_from_slice(params);
+        self
+    }
+}
+
+pub fn executor_lz_receive_option(gas_limit: u128) -> Vec<u8> {
+    OptionsBuilder::new().add_executor_lz_receive_option(gas_limit, 0).build()
+}
+
+pub fn executor_lz_compose_option(index: u16, gas_limit: u128) -> Vec<u8> {
+    OptionsBuilder::new()
+        .add_executor_lz_compose_option(index, gas_limit, 0)
+        .build()
+}
+
+fn checked_add(a: u128, b: u128) -> OptionsResult<u128> {
+    a.checked_add(b).ok_or(OptionsError::Overflow)
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> OptionsResult<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(OptionsError::InvalidSize);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> OptionsResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> OptionsResult<u16> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u128(&mut self) -> OptionsResult<u128> {
+        Ok(u128::from_be_bytes(self.take(16)?.try_into().unwrap()))
+    }
+
+    // the value of lzReceive and lzCompose options is only encoded if it is not zero
+    fn optional_u128(&mut self) -> OptionsResult<u128> {
+        if self.is_empty() {
+            Ok(0)
+        } else {
+            self.u128()
+        }
+    }
+
+    // legacy options encode uint256, which must fit in a u128
+    fn u256(&mut self) -> OptionsResult<u128> {
+        if self.u128()? != 0 {
+            return Err(OptionsError::Overflow);
+        }
+        self.u128()
+    }
+
+    fn bytes32(&mut self) -> OptionsResult<[u8; 32]> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+}
@@ -1,3 +1,6 @@
+use crate::errors::OFTError;
+use anchor_lang::prelude::{require, Result};
+
 const NONCE_OFFSET: usize = 0;
 const SRC_EID_OFFSET: usize = 8;
 const AMOUNT_LD_OFFSET: usize = 12;
@@ -18,34 +21,41 @@ pub fn encode(
     encoded
 }
 
-pub fn nonce(message: &[u8]) -> u64 {
+/// The message is [nonce][srcEid][amountLD][composeFrom][composeMsg].
+pub fn validate(message: &[u8]) -> Result<()> {
+    require!(message.len() >= COMPOSE_MSG_OFFSET, OFTError::InvalidMessage);
+    Ok(())
+}
+
+pub fn nonce(message: &[u8]) -> Result<u64> {
+    validate(message)?;
     let mut nonce_bytes = [0; 8];
     nonce_bytes.copy_from_slice(&message[NONCE_OFFSET..SRC_EID_OFFSET]);
-    u64::from_be_bytes(nonce_bytes)
+    Ok(u64::from_be_bytes(nonce_bytes))
 }
 
-pub fn src_eid(message: &[u8]) -> u32 {
+pub fn src_eid(message: &[u8]) -> Result<u32> {
+    validate(message)?;
     let mut src_eid_bytes = [0; 4];
     src_eid_bytes.copy_from_slice(&message[SRC_EID_OFFSET..AMOUNT_LD_OFFSET]);
-    u32::from_be_bytes(src_eid_bytes)
+    Ok(u32::from_be_bytes(src_eid_bytes))
 }
 
-pub fn amount_ld(message: &[u8]) -> u64 {
+pub fn amount_ld(message: &[u8]) -> Result<u64> {
+    validate(message)?;
     let mut amount_ld_bytes = [0; 8];
     amount_ld_bytes.copy_from_slice(&message[AMOUNT_LD_OFFSET..COMPOSE_FROM_OFFSET]);
-    u64::from_be_bytes(amount_ld_bytes)
+    Ok(u64::from_be_bytes(amount_ld_bytes))
 }
 
-pub fn compose_from(message: &[u8]) -> [u8; 32] {
+pub fn compose_from(message: &[u8]) -> Result<[u8; 32]> {
+    validate(message)?;
     let mut compose_from = [0; 32];
     compose_from.copy_from_slice(&message[COMPOSE_FROM_OFFSET..COMPOSE_MSG_OFFSET]);
-    compose_from
+    Ok(compose_from)
 }
 
-pub fn compose_msg(message: &[u8]) -> Vec<u8> {
-    if message.len() > COMPOSE_MSG_OFFSET {
-        message[COMPOSE_MSG_OFFSET..].to_vec()
-    } else {
-        Vec::new()
-    }
+pub fn compose_msg(message: &[u8]) -> Result<Vec<u8>> {
+    validate(message)?;
+    Ok(message[COMPOSE_MSG_OFFSET..].to_vec())
 }
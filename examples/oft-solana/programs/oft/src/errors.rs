@@ -11,4 +11,27 @@ pub enum OFTError {
     InvalidFee,
     InvalidMintAuthority,
     Paused,
+    InsufficientSubsidy,
+    InvalidTransferHookAccounts,
+    IncompatibleMintExtension,
+    EpochExceeded,
+    InvalidPathwayType,
+    MaxSupplyExceeded,
+    InvalidOFTType,
+    CreditLimitExceeded,
+    AmountOverflow,
+    TvlUnderflow,
+    InvalidClock,
+    InvalidMessage,
+    InvalidReferrer,
+    PeerNotSet,
+    InvalidRecipient,
+    ZeroAmount,
+    PeerInFlight,
+    InvalidNonce,
+    InvalidOptions,
+    LzReceiveGasTooLow,
+    NativeDropTooHigh,
+    ComposeGasTooHigh,
+    OrderedWithSecondarySender,
 }
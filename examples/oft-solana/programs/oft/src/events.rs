@@ -7,6 +7,16 @@ pub struct OFTSent {
     pub from: Pubkey,
     pub amount_sent_ld: u64,
     pub amount_received_ld: u64,
+    pub referrer: Option<Pubkey>,
+    pub to: [u8; 32],
+    pub authority: Pubkey, // the signer, the owner or delegate of `from`
+    // fee breakdown
+    pub oft_fee_ld: u64, // includes the referral fee
+    pub referral_fee_ld: u64,
+    pub transfer_fee_ld: u64, // Token2022 transfer fee
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+    pub composed: bool,
 }
 
 #[event]
@@ -15,4 +25,117 @@ pub struct OFTReceived {
     pub src_eid: u32,
     pub to: Pubkey,
     pub amount_received_ld: u64,
+    pub extensions: msg_codec::MsgExtensions, // empty for v1 messages
+    pub composed: bool,
+}
+
+#[event]
+pub struct OFTMintCapExceeded {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub to: Pubkey,
+    pub amount_ld: u64,
+    pub supply_ld: u64,
+    pub max_supply_ld: u64,
+}
+
+#[event]
+pub struct EscrowReconciled {
+    pub oft_store: Pubkey,
+    pub surplus_ld: u64,
+    pub accrued_fee_ld: u64,
+}
+
+#[event]
+pub struct OFTConfigSet {
+    pub oft_store: Pubkey,
+    pub params: SetOFTConfigParams,
+}
+
+#[event]
+pub struct PeerConfigSet {
+    pub oft_store: Pubkey,
+    pub remote_eid: u32,
+    pub config: PeerConfigParam,
+}
+
+#[event]
+pub struct PeerClosed {
+    pub oft_store: Pubkey,
+    pub remote_eid: u32,
+    pub peer_address: [u8; 32],
+    pub outstanding_sd: i128,
+    pub forced: bool,
+}
+
+#[event]
+pub struct PauseSet {
+    pub oft_store: Pubkey,
+    pub signer: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct FeeWithdrawn {
+    pub oft_store: Pubkey,
+    pub token_dest: Pubkey,
+    pub fee_ld: u64,
+}
+
+#[event]
+pub struct ReferralFeeClaimed {
+    pub oft_store: Pubkey,
+    pub referrer: Pubkey,
+    pub token_dest: Pubkey,
+    pub fee_ld: u64,
+}
+
+#[event]
+pub struct SubsidyVaultSet {
+    pub oft_store: Pubkey,
+    pub max_per_epoch: u64,
+}
+
+#[event]
+pub struct SubsidyWithdrawn {
+    pub oft_store: Pubkey,
+    pub receiver: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct PacketSkipped {
+    pub oft_store: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+}
+
+#[event]
+pub struct PacketNilified {
+    pub oft_store: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub payload_hash: [u8; 32],
+}
+
+#[event]
+pub struct PacketBurned {
+    pub oft_store: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+    pub amount_sd: u64,
+}
+
+#[event]
+pub struct PacketCleared {
+    pub oft_store: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+    pub amount_sd: u64,
 }
@@ -0,0 +1,99 @@
+use crate::*;
+use anchor_spl::token_interface::TokenAccount;
+
+/// Checks the accounting invariants of the OFT and returns every violation found. It does not fail
+/// on violations, so monitoring can simulate it.
+///
+/// remaining accounts: the peer configs of `params.peer_eids`, in the same order
+#[derive(Accounts)]
+pub struct AssertInvariants<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(address = oft_store.token_escrow)]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl AssertInvariants<'_> {
+    pub fn apply(
+        ctx: &Context<AssertInvariants>,
+        params: &AssertInvariantsParams,
+    ) -> Result<InvariantReport> {
+        let oft_store = &ctx.accounts.oft_store;
+        let escrow_balance_ld = ctx.accounts.token_escrow.amount;
+        let booked_ld = oft_store.booked_escrow_ld();
+
+        let mut violations = vec![];
+        if (escrow_balance_ld as u128) < booked_ld {
+            violations.push(InvariantViolation::EscrowShortfall {
+                shortfall_ld: (booked_ld - escrow_balance_ld as u128) as u64,
+            });
+        }
+        if oft_store.oft_type == OFTType::Native && oft_store.tvl_ld != 0 {
+            violations.push(InvariantViolation::NativeTvlNotZero { tvl_ld: oft_store.tvl_ld });
+        }
+
+        require!(
+            params.peer_eids.len() == ctx.remaining_accounts.len(),
+            ErrorCode::AccountNotEnoughKeys
+        );
+        for (eid, peer_info) in params.peer_eids.iter().zip(ctx.remaining_accounts) {
+            let (peer_address, _) = Pubkey::find_program_address(
+                &[PEER_SEED, oft_store.key().as_ref(), &eid.to_be_bytes()],
+                ctx.program_id,
+            );
+            require_keys_eq!(peer_info.key(), peer_address, ErrorCode::ConstraintSeeds);
+            require_keys_eq!(*peer_info.owner, ID, ErrorCode::AccountOwnedByWrongProgram);
+            let peer = PeerConfig::try_deserialize(&mut &peer_info.try_borrow_data()?[..])?;
+            for (inbound, rate_limiter) in
+                [(false, &peer.outbound_rate_limiter), (true, &peer.inbound_rate_limiter)]
+            {
+                if let Some(rate_limiter) = rate_limiter {
+                    if rate_limiter.tokens > rate_limiter.capacity {
+                        violations.push(InvariantViolation::RateLimiterOverCapacity {
+                            eid: *eid,
+                            inbound,
+                            tokens: rate_limiter.tokens,
+                            capacity: rate_limiter.capacity,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(InvariantReport {
+            escrow_balance_ld,
+            tvl_ld: oft_store.tvl_ld,
+            accrued_fee_ld: oft_store.accrued_fee_ld,
+            referral_fee_ld: oft_store.referral_fee_ld,
+            abandoned_ld: oft_store.abandoned_ld,
+            escrow_surplus_ld: (escrow_balance_ld as u128).saturating_sub(booked_ld) as u64,
+            violations,
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct AssertInvariantsParams {
+    pub peer_eids: Vec<u32>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InvariantReport {
+    pub escrow_balance_ld: u64,
+    pub tvl_ld: u64,
+    pub accrued_fee_ld: u64,
+    pub referral_fee_ld: u64,
+    pub abandoned_ld: u64,
+    pub escrow_surplus_ld: u64, // escrow balance not booked, see `reconcile`
+    pub violations: Vec<InvariantViolation>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum InvariantViolation {
+    EscrowShortfall { shortfall_ld: u64 },
+    NativeTvlNotZero { tvl_ld: u64 },
+    RateLimiterOverCapacity { eid: u32, inbound: bool, tokens: u64, capacity: u64 },
+}
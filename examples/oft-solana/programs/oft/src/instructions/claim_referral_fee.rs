@@ -0,0 +1,93 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimReferralFee<'info> {
+    #[account(address = referrer.referrer @OFTError::Unauthorized)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, oft_store.key().as_ref(), referrer.referrer.as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_dest: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimReferralFee<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, ClaimReferralFee<'info>>,
+        params: &ClaimReferralFeeParams,
+    ) -> Result<()> {
+        ctx.accounts.referrer.accrued_fee_ld = ctx
+            .accounts
+            .referrer
+            .accrued_fee_ld
+            .checked_sub(params.fee_ld)
+            .ok_or(OFTError::InvalidFee)?;
+        ctx.accounts.oft_store.referral_fee_ld = ctx
+            .accounts
+            .oft_store
+            .referral_fee_ld
+            .checked_sub(params.fee_ld)
+            .ok_or(OFTError::InvalidFee)?;
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.token_escrow.key().to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        // remaining accounts: [transfer hook accounts...]
+        let (hook_accounts, _) = transfer_hook::split_hook_accounts(
+            &ctx.accounts.token_mint.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+        transfer_hook::transfer_checked(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_escrow.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.token_dest.to_account_info(),
+            &ctx.accounts.oft_store.to_account_info(),
+            hook_accounts,
+            params.fee_ld,
+            ctx.accounts.token_mint.decimals,
+            &[&seeds],
+        )?;
+        emit!(ReferralFeeClaimed {
+            oft_store: ctx.accounts.oft_store.key(),
+            referrer: ctx.accounts.referrer.referrer,
+            token_dest: ctx.accounts.token_dest.key(),
+            fee_ld: params.fee_ld,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimReferralFeeParams {
+    pub fee_ld: u64,
+}
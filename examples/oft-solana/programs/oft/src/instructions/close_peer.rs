@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: ClosePeerParams)]
+pub struct ClosePeer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl ClosePeer<'_> {
+    pub fn apply(ctx: &mut Context<ClosePeer>, params: &ClosePeerParams) -> Result<()> {
+        let peer = &ctx.accounts.peer;
+        if !params.force {
+            // value is in flight if the outstanding supply is not settled or a limiter is refilling
+            let current_time = current_timestamp()?;
+            let refilling = [&peer.outbound_rate_limiter, &peer.inbound_rate_limiter]
+                .into_iter()
+                .flatten()
+                .any(|rate_limiter| rate_limiter.tokens_at(current_time) < rate_limiter.capacity);
+            require!(peer.outstanding_sd() == 0 && !refilling, OFTError::PeerInFlight);
+        }
+        emit!(PeerClosed {
+            oft_store: ctx.accounts.oft_store.key(),
+            remote_eid: params.remote_eid,
+            peer_address: peer.peer_address,
+            outstanding_sd: peer.outstanding_sd(),
+            forced: params.force,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClosePeerParams {
+    pub remote_eid: u32,
+    pub force: bool, // close even if value is in flight
+}
@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: GetPeerAccountingParams)]
+pub struct GetPeerAccounting<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl GetPeerAccounting<'_> {
+    pub fn apply(
+        ctx: &Context<GetPeerAccounting>,
+        _params: &GetPeerAccountingParams,
+    ) -> Result<PeerAccounting> {
+        let peer = &ctx.accounts.peer;
+        Ok(PeerAccounting {
+            total_sent_sd: peer.total_sent_sd,
+            total_received_sd: peer.total_received_sd,
+            outstanding_sd: peer.outstanding_sd(),
+            credit_limit_sd: peer.credit_limit_sd,
+            remaining_credit_sd: peer.remaining_credit_sd(),
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetPeerAccountingParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerAccounting {
+    pub total_sent_sd: u128,
+    pub total_received_sd: u128,
+    pub outstanding_sd: i128,
+    pub credit_limit_sd: Option<u64>,
+    pub remaining_credit_sd: Option<u128>,
+}
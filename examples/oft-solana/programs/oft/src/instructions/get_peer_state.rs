@@ -0,0 +1,111 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: GetPeerStateParams)]
+pub struct GetPeerState<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl GetPeerState<'_> {
+    pub fn apply(ctx: &Context<GetPeerState>, _params: &GetPeerStateParams) -> Result<PeerState> {
+        let current_time = current_timestamp()?;
+        let peer = &ctx.accounts.peer;
+        let oft_store = &ctx.accounts.oft_store;
+        let paused = oft_store.paused;
+        let mut msg_types: Vec<u16> = peer
+            .enforced_options_by_type
+            .iter()
+            .chain(&oft_store.default_enforced_options)
+            .map(|entry| entry.msg_type)
+            .filter(|msg_type| *msg_type != MSG_TYPE_SEND && *msg_type != MSG_TYPE_SEND_AND_CALL)
+            .collect();
+        msg_types.sort_unstable();
+        msg_types.dedup();
+        Ok(PeerState {
+            peer_address: peer.peer_address,
+            fee_bps: peer.fee_bps.unwrap_or(oft_store.default_fee_bps),
+            enforced_options: EnforcedOptions {
+                send: peer.get_enforced_options(oft_store, MSG_TYPE_SEND),
+                send_and_call: peer.get_enforced_options(oft_store, MSG_TYPE_SEND_AND_CALL),
+            },
+            enforced_options_by_type: msg_types
+                .into_iter()
+                .map(|msg_type| EnforcedOptionsEntry {
+                    msg_type,
+                    options: peer.get_enforced_options(oft_store, msg_type),
+                })
+                .collect(),
+            secondary_sender: peer.secondary_sender.clone(),
+            outbound: DirectionState {
+                paused,
+                rate_limit: peer
+                    .outbound_rate_limiter
+                    .as_ref()
+                    .map(|rate_limiter| RateLimitState::at(rate_limiter, current_time)),
+            },
+            inbound: DirectionState {
+                paused,
+                rate_limit: peer
+                    .inbound_rate_limiter
+                    .as_ref()
+                    .map(|rate_limiter| RateLimitState::at(rate_limiter, current_time)),
+            },
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetPeerStateParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerState {
+    pub peer_address: [u8; 32],
+    pub fee_bps: u16, // the peer fee if set, otherwise the default fee
+    // the enforced options send combines with the extra options: the peer's if set, otherwise the
+    // store defaults
+    pub enforced_options: EnforcedOptions,
+    pub enforced_options_by_type: Vec<EnforcedOptionsEntry>, // sorted by msg type
+    pub secondary_sender: Option<SecondarySender>,
+    pub outbound: DirectionState,
+    pub inbound: DirectionState,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DirectionState {
+    pub paused: bool,
+    pub rate_limit: Option<RateLimitState>, // None if the direction is not rate limited
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitState {
+    pub capacity: u64,
+    pub available: u64, // the amount that can be consumed at the current clock
+    pub refill_per_second: u64,
+    pub seconds_to_full: Option<u64>, // None if it never refills
+}
+
+impl RateLimitState {
+    fn at(rate_limiter: &RateLimiter, current_time: u64) -> Self {
+        Self {
+            capacity: rate_limiter.capacity,
+            available: rate_limiter.tokens_at(current_time),
+            refill_per_second: rate_limiter.refill_per_second,
+            seconds_to_full: rate_limiter.seconds_to_full(current_time),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::*;
+use anchor_spl::{
+    token_2022::Token2022,
+    token_2022_extensions::transfer_fee::{
+        harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint,
+    },
+    token_interface::{Mint, TokenAccount},
+};
+
+/// Moves the Token2022 transfer fees withheld in the escrow to the mint, where the mint's
+/// withdraw withheld authority can collect them. Anyone can call it.
+#[derive(Accounts)]
+pub struct HarvestWithheldFees<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl HarvestWithheldFees<'_> {
+    pub fn apply(ctx: &mut Context<HarvestWithheldFees>) -> Result<()> {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            vec![ctx.accounts.token_escrow.to_account_info()],
+        )
+    }
+}
@@ -1,5 +1,16 @@
 use crate::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{
+            default_account_state::DefaultAccountState, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
+        },
+        solana_program::program_option::COption,
+        state::{AccountState, Mint as MintState, Multisig},
+    },
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 use oapp::endpoint::{instructions::RegisterOAppParams, ID as ENDPOINT_ID};
 
 #[derive(Accounts)]
@@ -34,6 +45,10 @@ pub struct InitOFT<'info> {
     pub token_escrow: InterfaceAccount<'info, TokenAccount>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    /// CHECK: only required for Native type, validated in `validate_mint_authority`. It comes last, so
+    /// that the accounts before it keep their positions.
+    #[account(constraint = token_mint.mint_authority == COption::Some(mint_authority.key()) @OFTError::InvalidMintAuthority)]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
 }
 
 impl InitOFT<'_> {
@@ -44,8 +59,9 @@ impl InitOFT<'_> {
             ctx.accounts.token_mint.decimals >= params.shared_decimals,
             OFTError::InvalidDecimals
         );
-        ctx.accounts.oft_store.ld2sd_rate =
-            10u64.pow((ctx.accounts.token_mint.decimals - params.shared_decimals) as u32);
+        ctx.accounts.oft_store.ld2sd_rate = 10u64
+            .checked_pow((ctx.accounts.token_mint.decimals - params.shared_decimals) as u32)
+            .ok_or(OFTError::InvalidDecimals)?;
         ctx.accounts.oft_store.token_mint = ctx.accounts.token_mint.key();
         ctx.accounts.oft_store.token_escrow = ctx.accounts.token_escrow.key();
         ctx.accounts.oft_store.endpoint_program =
@@ -61,10 +77,29 @@ impl InitOFT<'_> {
         ctx.accounts.oft_store.paused = false;
         ctx.accounts.oft_store.pauser = None;
         ctx.accounts.oft_store.unpauser = None;
+        ctx.accounts.oft_store.max_supply_ld = None;
+        ctx.accounts.oft_store.accrued_fee_ld = 0;
+        ctx.accounts.oft_store.referral_fee_share_bps = 0;
+        ctx.accounts.oft_store.referral_fee_ld = 0;
+        ctx.accounts.oft_store.default_enforced_options = vec![];
+        ctx.accounts.oft_store.abandoned_ld = 0;
+        ctx.accounts.oft_store.mint_flags =
+            validate_mint_extensions(&ctx.accounts.token_mint.to_account_info(), &params.oft_type)?;
+        if params.oft_type != OFTType::Adapter {
+            validate_mint_authority(
+                ctx.accounts.oft_store.key(),
+                ctx.accounts.mint_authority.as_ref().map(|a| a.to_account_info()),
+                &ctx.accounts.token_program.key(),
+            )?;
+        }
 
         // Initialize the lz_receive_types_accounts
         ctx.accounts.lz_receive_types_accounts.oft_store = ctx.accounts.oft_store.key();
         ctx.accounts.lz_receive_types_accounts.token_mint = ctx.accounts.token_mint.key();
+        ctx.accounts.lz_receive_types_accounts.transfer_hook_extra_metas =
+            transfer_hook::get_hook_extra_metas_address(
+                &ctx.accounts.token_mint.to_account_info(),
+            )?;
 
         // Register the oapp
         oapp::endpoint_cpi::register_oapp(
@@ -84,3 +119,65 @@ pub struct InitOFTParams {
     pub shared_decimals: u8,
     pub endpoint_program: Option<Pubkey>,
 }
+
+/// Rejects mints with extensions the OFT cannot work with and returns the `MINT_FLAG_*` flags of
+/// the risky ones that are allowed. Extensions unknown to this program are rejected.
+/// Hybrid stores do not support transfer fees, as `lz_receive_types` can not tell the pathways
+/// apart.
+pub fn validate_mint_extensions(token_mint: &AccountInfo, oft_type: &OFTType) -> Result<u8> {
+    let token_mint_data = token_mint.try_borrow_data()?;
+    let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
+    let extension_types = token_mint_ext
+        .get_extension_types()
+        .map_err(|_| error!(OFTError::IncompatibleMintExtension))?;
+
+    let mut mint_flags = 0;
+    for extension_type in extension_types {
+        match extension_type {
+            ExtensionType::NonTransferable
+            | ExtensionType::ConfidentialTransferMint
+            | ExtensionType::ConfidentialTransferFeeConfig
+            | ExtensionType::ConfidentialMintBurn => {
+                return Err(OFTError::IncompatibleMintExtension.into());
+            },
+            ExtensionType::TransferFeeConfig => {
+                require!(*oft_type != OFTType::Hybrid, OFTError::IncompatibleMintExtension);
+            },
+            ExtensionType::DefaultAccountState => {
+                let default_state = token_mint_ext.get_extension::<DefaultAccountState>()?;
+                require!(
+                    default_state.state != AccountState::Frozen as u8,
+                    OFTError::IncompatibleMintExtension
+                );
+            },
+            ExtensionType::PermanentDelegate => mint_flags |= MINT_FLAG_PERMANENT_DELEGATE,
+            ExtensionType::MintCloseAuthority => mint_flags |= MINT_FLAG_CLOSE_AUTHORITY,
+            ExtensionType::TransferHook => mint_flags |= MINT_FLAG_TRANSFER_HOOK,
+            _ => {},
+        }
+    }
+    Ok(mint_flags)
+}
+
+/// The mint authority of a Native or Hybrid OFT must be the oft_store itself, or a 1-of-n spl-token
+/// multisig with the oft_store as one of the signers.
+fn validate_mint_authority(
+    oft_store: Pubkey,
+    mint_authority: Option<AccountInfo>,
+    token_program: &Pubkey,
+) -> Result<()> {
+    let mint_authority = mint_authority.ok_or(OFTError::InvalidMintAuthority)?;
+    if mint_authority.key() == oft_store {
+        return Ok(());
+    }
+    require!(
+        mint_authority.owner == token_program && mint_authority.data_len() == Multisig::LEN,
+        OFTError::InvalidMintAuthority
+    );
+    let multisig = Multisig::unpack(&mint_authority.try_borrow_data()?)?;
+    require!(
+        multisig.m == 1 && multisig.signers[..multisig.n as usize].contains(&oft_store),
+        OFTError::InvalidMintAuthority
+    );
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: InitReferrerParams)]
+pub struct InitReferrer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Referrer::INIT_SPACE,
+        seeds = [REFERRER_SEED, oft_store.key().as_ref(), params.referrer.as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitReferrer<'_> {
+    pub fn apply(ctx: &mut Context<InitReferrer>, params: &InitReferrerParams) -> Result<()> {
+        ctx.accounts.referrer.referrer = params.referrer;
+        ctx.accounts.referrer.accrued_fee_ld = 0;
+        ctx.accounts.referrer.bump = ctx.bumps.referrer;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitReferrerParams {
+    pub referrer: Pubkey,
+}
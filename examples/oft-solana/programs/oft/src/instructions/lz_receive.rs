@@ -3,7 +3,7 @@ use anchor_lang::solana_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_2022::spl_token_2022::{self, solana_program::program_option::COption},
-    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use oapp::endpoint::{
     cpi::accounts::Clear,
@@ -25,7 +25,7 @@ pub struct LzReceive<'info> {
             &params.src_eid.to_be_bytes()
         ],
         bump = peer.bump,
-        constraint = peer.peer_address == params.sender @OFTError::InvalidSender
+        constraint = peer.accepts_sender(&params.sender)? @OFTError::InvalidSender
     )]
     pub peer: Account<'info, PeerConfig>,
     #[account(
@@ -43,7 +43,7 @@ pub struct LzReceive<'info> {
     )]
     pub token_escrow: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: the wallet address to receive the token
-    #[account(address = Pubkey::from(msg_codec::send_to(&params.message)) @OFTError::InvalidTokenDest)]
+    #[account(address = Pubkey::from(msg_codec::send_to(&params.message)?) @OFTError::InvalidTokenDest)]
     pub to_address: AccountInfo<'info>,
     #[account(
         init_if_needed,
@@ -69,15 +69,30 @@ pub struct LzReceive<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl LzReceive<'_> {
-    pub fn apply(ctx: &mut Context<LzReceive>, params: &LzReceiveParams) -> Result<()> {
+impl<'info> LzReceive<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, LzReceive<'info>>,
+        params: &LzReceiveParams,
+    ) -> Result<()> {
         require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        ctx.accounts.peer.record_nonce(params.nonce)?;
 
         let oft_store_seed = ctx.accounts.token_escrow.key();
         let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[ctx.accounts.oft_store.bump]];
 
+        // remaining accounts: [transfer hook accounts (not Native)..., clear..., compose...]
+        let (hook_accounts, endpoint_accounts) =
+            if ctx.accounts.oft_store.oft_type != OFTType::Native {
+                transfer_hook::split_hook_accounts(
+                    &ctx.accounts.token_mint.to_account_info(),
+                    ctx.remaining_accounts,
+                )?
+            } else {
+                (&[][..], ctx.remaining_accounts)
+            };
+
         // Validate and clear the payload
-        let accounts_for_clear = &ctx.remaining_accounts[0..Clear::MIN_ACCOUNTS_LEN];
+        let accounts_for_clear = &endpoint_accounts[0..Clear::MIN_ACCOUNTS_LEN];
         let _ = oapp::endpoint_cpi::clear(
             ctx.accounts.oft_store.endpoint_program,
             ctx.accounts.oft_store.key(),
@@ -93,9 +108,14 @@ impl LzReceive<'_> {
             },
         )?;
 
+        // v2 messages carry the extensions trailer after the v1 message
+        let (message, extensions) =
+            msg_codec::split_extensions(ctx.accounts.peer.msg_version, &params.message)?;
+
         // Convert the amount from sd to ld
-        let amount_sd = msg_codec::amount_sd(&params.message);
-        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
+        let amount_sd = msg_codec::amount_sd(message)?;
+        ctx.accounts.peer.record_received(amount_sd)?;
+        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
 
         // Consume the inbound rate limiter
         if let Some(rate_limiter) = ctx.accounts.peer.inbound_rate_limiter.as_mut() {
@@ -106,22 +126,19 @@ impl LzReceive<'_> {
             rate_limiter.refill(amount_received_ld)?;
         }
 
-        if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
+        if ctx.accounts.oft_store.pathway_type(&ctx.accounts.peer)? == OFTType::Adapter {
             // unlock from escrow
-            ctx.accounts.oft_store.tvl_ld -= amount_received_ld;
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.token_escrow.to_account_info(),
-                        mint: ctx.accounts.token_mint.to_account_info(),
-                        to: ctx.accounts.token_dest.to_account_info(),
-                        authority: ctx.accounts.oft_store.to_account_info(),
-                    },
-                )
-                .with_signer(&[&seeds]),
+            ctx.accounts.oft_store.sub_tvl(amount_received_ld)?;
+            transfer_hook::transfer_checked(
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.token_escrow.to_account_info(),
+                &ctx.accounts.token_mint.to_account_info(),
+                &ctx.accounts.token_dest.to_account_info(),
+                &ctx.accounts.oft_store.to_account_info(),
+                hook_accounts,
                 amount_received_ld,
                 ctx.accounts.token_mint.decimals,
+                &[&seeds],
             )?;
 
             // update the amount_received_ld with the post transfer fee amount
@@ -129,6 +146,22 @@ impl LzReceive<'_> {
                 get_post_fee_amount_ld(&ctx.accounts.token_mint, amount_received_ld)?
         } else if let Some(mint_authority) = &ctx.accounts.mint_authority {
             // Native type
+            // the event is logged even though the transaction fails, so monitoring can catch it
+            if !ctx
+                .accounts
+                .oft_store
+                .can_mint(ctx.accounts.token_mint.supply, amount_received_ld)
+            {
+                emit!(OFTMintCapExceeded {
+                    guid: params.guid,
+                    src_eid: params.src_eid,
+                    to: ctx.accounts.to_address.key(),
+                    amount_ld: amount_received_ld,
+                    supply_ld: ctx.accounts.token_mint.supply,
+                    max_supply_ld: ctx.accounts.oft_store.max_supply_ld.unwrap_or_default(),
+                });
+                return Err(OFTError::MaxSupplyExceeded.into());
+            }
             // mint
             let ix = spl_token_2022::instruction::mint_to(
                 ctx.accounts.token_program.key,
@@ -152,11 +185,13 @@ impl LzReceive<'_> {
             return Err(OFTError::InvalidMintAuthority.into());
         }
 
-        if let Some(message) = msg_codec::compose_msg(&params.message) {
+        let compose_msg = msg_codec::compose_msg(message)?;
+        let composed = compose_msg.is_some();
+        if let Some(message) = compose_msg {
             oapp::endpoint_cpi::send_compose(
                 ctx.accounts.oft_store.endpoint_program,
                 ctx.accounts.oft_store.key(),
-                &ctx.remaining_accounts[Clear::MIN_ACCOUNTS_LEN..],
+                &endpoint_accounts[Clear::MIN_ACCOUNTS_LEN..],
                 seeds,
                 SendComposeParams {
                     to: ctx.accounts.to_address.key(),
@@ -177,6 +212,8 @@ impl LzReceive<'_> {
             src_eid: params.src_eid,
             to: ctx.accounts.to_address.key(),
             amount_received_ld,
+            extensions,
+            composed,
         });
         Ok(())
     }
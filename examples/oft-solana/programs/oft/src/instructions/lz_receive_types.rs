@@ -33,8 +33,12 @@ pub struct LzReceiveTypes<'info> {
 // account 11 - event authority
 // account 12 - this program
 // account remaining accounts
-//      0..9 - accounts for clear
-//      9..16 - accounts for compose
+//      0..n - transfer hook accounts (Adapter or Hybrid with a transfer hook mint only)
+//      n..n+9 - accounts for clear
+//      n+9..n+16 - accounts for compose
+//
+// the transfer hook extra account metas account stored in LzReceiveTypesAccounts is passed as the
+// first remaining account of this instruction. the peer may be passed after it, see msg_version
 impl LzReceiveTypes<'_> {
     pub fn apply(
         ctx: &Context<LzReceiveTypes>,
@@ -58,7 +62,7 @@ impl LzReceiveTypes<'_> {
         ];
 
         // account 4..9
-        let to_address = Pubkey::from(msg_codec::send_to(&params.message));
+        let to_address = Pubkey::from(msg_codec::send_to(&params.message)?);
         let token_program = ctx.accounts.token_mint.to_account_info().owner;
         let token_dest = get_associated_token_address_with_program_id(
             &to_address,
@@ -97,8 +101,25 @@ impl LzReceiveTypes<'_> {
             LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false }, // 12
         ]);
 
+        let amount_sd = msg_codec::amount_sd(&params.message)?;
+        let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
+
+        // remaining accounts 0..n
+        // the peer is not loaded here, so Hybrid stores always pass the hook accounts
+        if ctx.accounts.oft_store.oft_type != OFTType::Native {
+            let accounts_for_hook = transfer_hook::get_hook_lz_accounts(
+                &ctx.accounts.token_mint.to_account_info(),
+                ctx.remaining_accounts.first(),
+                ctx.accounts.oft_store.token_escrow,
+                token_dest,
+                ctx.accounts.oft_store.key(),
+                amount_ld,
+            )?;
+            accounts.extend(accounts_for_hook);
+        }
+
         let endpoint_program = ctx.accounts.oft_store.endpoint_program;
-        // remaining accounts 0..9
+        // remaining accounts n..n+9
         let accounts_for_clear = oapp::endpoint_cpi::get_accounts_for_clear(
             endpoint_program,
             &ctx.accounts.oft_store.key(),
@@ -108,10 +129,22 @@ impl LzReceiveTypes<'_> {
         );
         accounts.extend(accounts_for_clear);
 
-        // remaining accounts 9..16
-        if let Some(message) = msg_codec::compose_msg(&params.message) {
-            let amount_sd = msg_codec::amount_sd(&params.message);
-            let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
+        // remaining accounts n+9..n+16
+        // the compose msg excludes the extensions trailer of v2 messages, the same as lz_receive.
+        // without the peer, a trailer is assumed to be an extensions trailer
+        let peer_account = ctx.remaining_accounts.iter().find(|account| account.key() == peer);
+        let msg_version = match peer_account {
+            Some(account) => {
+                PeerConfig::try_deserialize(&mut &account.try_borrow_data()?[..])?.msg_version
+            },
+            None => msg_codec::MSG_VERSION_2,
+        };
+        let compose_msg = msg_codec::split_extensions(msg_version, &params.message)
+            .and_then(|(message, _)| msg_codec::compose_msg(message))
+            .ok()
+            .flatten();
+        if let Some(message) = compose_msg {
+            // Hybrid mints have no transfer fee, so the amount is the same on both pathways
             let amount_received_ld = if ctx.accounts.oft_store.oft_type == OFTType::Native {
                 amount_ld
             } else {
@@ -0,0 +1,174 @@
+use crate::*;
+use anchor_lang::solana_program::keccak;
+use oapp::endpoint::instructions::{BurnParams, ClearParams, NilifyParams, SkipParams};
+
+/// Lets the admin unblock an inbound pathway through the endpoint, signed by the OFT store.
+/// Burned and cleared packets are never delivered, so their amount, checked against the payload
+/// hash, is settled in the peer accounting and, for Adapter pathways, moved from the tvl to
+/// `abandoned_ld`. A skipped packet has no verified amount, so its amount stays in the tvl.
+///
+/// remaining accounts: the endpoint accounts of the action
+#[derive(Accounts)]
+#[instruction(params: ManagePacketParams)]
+pub struct ManagePacket<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.src_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl ManagePacket<'_> {
+    pub fn apply(ctx: &mut Context<ManagePacket>, params: &ManagePacketParams) -> Result<()> {
+        let oft_store = ctx.accounts.oft_store.key();
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            ctx.accounts.oft_store.token_escrow.as_ref(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        let endpoint_program = ctx.accounts.oft_store.endpoint_program;
+        match &params.action {
+            PacketAction::Skip => {
+                oapp::endpoint_cpi::skip(
+                    endpoint_program,
+                    oft_store,
+                    ctx.remaining_accounts,
+                    seeds,
+                    SkipParams {
+                        receiver: oft_store,
+                        src_eid: params.src_eid,
+                        sender: params.sender,
+                        nonce: params.nonce,
+                    },
+                )?;
+                Self::advance_nonce(ctx, params.nonce);
+                emit!(PacketSkipped {
+                    oft_store,
+                    src_eid: params.src_eid,
+                    sender: params.sender,
+                    nonce: params.nonce,
+                });
+            },
+            PacketAction::Nilify { payload_hash } => {
+                oapp::endpoint_cpi::nilify(
+                    endpoint_program,
+                    oft_store,
+                    ctx.remaining_accounts,
+                    seeds,
+                    NilifyParams {
+                        receiver: oft_store,
+                        src_eid: params.src_eid,
+                        sender: params.sender,
+                        nonce: params.nonce,
+                        payload_hash: *payload_hash,
+                    },
+                )?;
+                emit!(PacketNilified {
+                    oft_store,
+                    src_eid: params.src_eid,
+                    sender: params.sender,
+                    nonce: params.nonce,
+                    payload_hash: *payload_hash,
+                });
+            },
+            PacketAction::Burn { guid, message } => {
+                oapp::endpoint_cpi::burn(
+                    endpoint_program,
+                    oft_store,
+                    ctx.remaining_accounts,
+                    seeds,
+                    BurnParams {
+                        receiver: oft_store,
+                        src_eid: params.src_eid,
+                        sender: params.sender,
+                        nonce: params.nonce,
+                        payload_hash: keccak::hash(&[&guid[..], message].concat()).to_bytes(),
+                    },
+                )?;
+                let amount_sd = msg_codec::amount_sd(message)?;
+                Self::settle(ctx, params.nonce, amount_sd)?;
+                emit!(PacketBurned {
+                    oft_store,
+                    src_eid: params.src_eid,
+                    sender: params.sender,
+                    nonce: params.nonce,
+                    guid: *guid,
+                    amount_sd,
+                });
+            },
+            PacketAction::Clear { guid, message } => {
+                oapp::endpoint_cpi::clear(
+                    endpoint_program,
+                    oft_store,
+                    ctx.remaining_accounts,
+                    seeds,
+                    ClearParams {
+                        receiver: oft_store,
+                        src_eid: params.src_eid,
+                        sender: params.sender,
+                        nonce: params.nonce,
+                        guid: *guid,
+                        message: message.clone(),
+                    },
+                )?;
+                let amount_sd = msg_codec::amount_sd(message)?;
+                Self::settle(ctx, params.nonce, amount_sd)?;
+                emit!(PacketCleared {
+                    oft_store,
+                    src_eid: params.src_eid,
+                    sender: params.sender,
+                    nonce: params.nonce,
+                    guid: *guid,
+                    amount_sd,
+                });
+            },
+        }
+        Ok(())
+    }
+
+    /// Settles an undelivered inbound amount as if it was received, without crediting anyone.
+    /// The escrowed amount stays booked as abandoned, so it is never swept into the fees.
+    fn settle(ctx: &mut Context<ManagePacket>, nonce: u64, amount_sd: u64) -> Result<()> {
+        ctx.accounts.peer.record_abandoned(amount_sd);
+        Self::advance_nonce(ctx, nonce);
+        if ctx.accounts.oft_store.pathway_type(&ctx.accounts.peer)? == OFTType::Adapter {
+            let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
+            ctx.accounts.oft_store.sub_tvl(amount_ld)?;
+            ctx.accounts.oft_store.add_abandoned(amount_ld)?;
+        }
+        Ok(())
+    }
+
+    // an ordered pathway moves past the abandoned nonce if it is the next one
+    fn advance_nonce(ctx: &mut Context<ManagePacket>, nonce: u64) {
+        let peer = &mut ctx.accounts.peer;
+        if peer.ordered_inbound && Some(nonce) == peer.inbound_nonce.checked_add(1) {
+            peer.inbound_nonce = nonce;
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ManagePacketParams {
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub action: PacketAction,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum PacketAction {
+    Skip,
+    Nilify { payload_hash: [u8; 32] },
+    Burn { guid: [u8; 32], message: Vec<u8> },
+    Clear { guid: [u8; 32], message: Vec<u8> },
+}
@@ -1,21 +1,49 @@
+pub mod assert_invariants;
+pub mod claim_referral_fee;
+pub mod close_peer;
+pub mod get_peer_accounting;
+pub mod get_peer_state;
+pub mod harvest_withheld_fees;
 pub mod init_oft;
+pub mod init_referrer;
 pub mod lz_receive;
 pub mod lz_receive_types;
+pub mod manage_packet;
+pub mod next_nonce;
 pub mod quote_oft;
 pub mod quote_send;
+pub mod realloc_account;
+pub mod reconcile;
 pub mod send;
 pub mod set_oft_config;
 pub mod set_pause;
 pub mod set_peer_config;
+pub mod set_subsidy_vault;
+pub mod validate_send;
 pub mod withdraw_fee;
+pub mod withdraw_subsidy;
 
+pub use assert_invariants::*;
+pub use claim_referral_fee::*;
+pub use close_peer::*;
+pub use get_peer_accounting::*;
+pub use get_peer_state::*;
+pub use harvest_withheld_fees::*;
 pub use init_oft::*;
+pub use init_referrer::*;
 pub use lz_receive::*;
 pub use lz_receive_types::*;
+pub use manage_packet::*;
+pub use next_nonce::*;
 pub use quote_oft::*;
 pub use quote_send::*;
+pub use realloc_account::*;
+pub use reconcile::*;
 pub use send::*;
 pub use set_oft_config::*;
 pub use set_pause::*;
 pub use set_peer_config::*;
+pub use set_subsidy_vault::*;
+pub use validate_send::*;
 pub use withdraw_fee::*;
+pub use withdraw_subsidy::*;
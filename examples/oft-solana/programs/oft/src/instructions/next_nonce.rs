@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Returns the nonce expected next from an ordered inbound pathway, or 0 if it is unordered.
+#[derive(Accounts)]
+#[instruction(params: NextNonceParams)]
+pub struct NextNonce<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.src_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl NextNonce<'_> {
+    pub fn apply(ctx: &Context<NextNonce>, params: &NextNonceParams) -> Result<u64> {
+        let peer = &ctx.accounts.peer;
+        if peer.ordered_inbound && peer.accepts_sender(&params.sender)? {
+            Ok(peer.inbound_nonce.checked_add(1).ok_or(OFTError::InvalidNonce)?)
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct NextNonceParams {
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+}
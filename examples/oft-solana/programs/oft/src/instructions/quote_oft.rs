@@ -25,33 +25,79 @@ pub struct QuoteOFT<'info> {
 impl QuoteOFT<'_> {
     pub fn apply(ctx: &Context<QuoteOFT>, params: &QuoteOFTParams) -> Result<QuoteOFTResult> {
         require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        validate_transfer(&ctx.accounts.peer, &params.to)?;
 
-        let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
-            params.amount_ld,
-            &ctx.accounts.oft_store,
-            &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
-        )?;
-        require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
+        let epoch = Clock::get()?.epoch;
+        let (oft_fee_details, oft_receipt) = Self::quote_at_epoch(ctx, params, epoch)?;
+        require!(oft_receipt.amount_received_ld > 0, OFTError::ZeroAmount);
+        require!(
+            oft_receipt.amount_received_ld >= params.min_amount_ld,
+            OFTError::SlippageExceeded
+        );
 
-        let oft_limits = OFTLimits { min_amount_ld: 0, max_amount_ld: 0xffffffffffffffff };
-        let mut oft_fee_details = if amount_received_ld + oft_fee_ld < amount_sent_ld {
+        // a newer Token2022 transfer fee may activate before the send is executed
+        let transfer_fee_activation_epoch =
+            get_transfer_fee_activation_epoch(&ctx.accounts.token_mint, epoch)?;
+        let (next_epoch_oft_fee_details, next_epoch_oft_receipt) =
+            Self::quote_at_epoch(ctx, params, epoch + 1)?;
+
+        let oft_limits = OFTLimits {
+            min_amount_ld: 0,
+            max_amount_ld: 0xffffffffffffffff,
+            mint_capacity_ld: ctx
+                .accounts
+                .oft_store
+                .mint_capacity_ld(ctx.accounts.token_mint.supply),
+        };
+        Ok(QuoteOFTResult {
+            oft_limits,
+            oft_fee_details,
+            oft_receipt,
+            epoch,
+            next_epoch_oft_fee_details,
+            next_epoch_oft_receipt,
+            transfer_fee_activation_epoch,
+        })
+    }
+
+    fn quote_at_epoch(
+        ctx: &Context<QuoteOFT>,
+        params: &QuoteOFTParams,
+        epoch: u64,
+    ) -> Result<(Vec<OFTFeeDetail>, OFTReceipt)> {
+        let (amount_sent_ld, amount_received_ld, oft_fee_ld) =
+            compute_fee_and_adjust_amount_at_epoch(
+                params.amount_ld,
+                &ctx.accounts.oft_store,
+                &ctx.accounts.token_mint,
+                &ctx.accounts.peer,
+                epoch,
+            )?;
+
+        // for Native pathways, the transfer fee is only charged on the oft fee moved to the escrow
+        let transfer_fee_ld =
+            if ctx.accounts.oft_store.pathway_type(&ctx.accounts.peer)? == OFTType::Adapter {
+                amount_sent_ld - oft_fee_ld - amount_received_ld
+            } else {
+                get_transfer_fee_ld(&ctx.accounts.token_mint, oft_fee_ld, epoch)?
+            };
+        let mut oft_fee_details = if transfer_fee_ld > 0 {
             vec![OFTFeeDetail {
-                fee_amount_ld: amount_sent_ld - oft_fee_ld - amount_received_ld,
+                fee_amount_ld: transfer_fee_ld,
                 description: "Token2022 Transfer Fee".to_string(),
             }]
         } else {
             vec![]
         };
         // cross chain fee
-        if oft_fee_ld > 0 {
+        let cross_chain_fee_ld = amount_sent_ld - amount_received_ld - transfer_fee_ld;
+        if cross_chain_fee_ld > 0 {
             oft_fee_details.push(OFTFeeDetail {
-                fee_amount_ld: oft_fee_ld,
+                fee_amount_ld: cross_chain_fee_ld,
                 description: "Cross Chain Fee".to_string(),
             });
         }
-        let oft_receipt = OFTReceipt { amount_sent_ld, amount_received_ld };
-        Ok(QuoteOFTResult { oft_limits, oft_fee_details, oft_receipt })
+        Ok((oft_fee_details, OFTReceipt { amount_sent_ld, amount_received_ld }))
     }
 }
 
@@ -71,6 +117,10 @@ pub struct QuoteOFTResult {
     pub oft_limits: OFTLimits,
     pub oft_fee_details: Vec<OFTFeeDetail>,
     pub oft_receipt: OFTReceipt,
+    pub epoch: u64, // the epoch of oft_fee_details and oft_receipt
+    pub next_epoch_oft_fee_details: Vec<OFTFeeDetail>,
+    pub next_epoch_oft_receipt: OFTReceipt,
+    pub transfer_fee_activation_epoch: Option<u64>, // None if no newer transfer fee is pending
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -89,4 +139,5 @@ pub struct OFTReceipt {
 pub struct OFTLimits {
     pub min_amount_ld: u64,
     pub max_amount_ld: u64,
+    pub mint_capacity_ld: Option<u64>, // the amount that can still be received under the supply cap
 }
@@ -31,79 +31,128 @@ pub struct QuoteSend<'info> {
     pub peer: Account<'info, PeerConfig>,
     #[account(address = oft_store.token_mint)]
     pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [SUBSIDY_VAULT_SEED, oft_store.key().as_ref()],
+        bump = subsidy_vault.bump
+    )]
+    pub subsidy_vault: Option<Account<'info, SubsidyVault>>,
 }
 
 impl QuoteSend<'_> {
     pub fn apply(ctx: &Context<QuoteSend>, params: &QuoteSendParams) -> Result<MessagingFee> {
         require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        validate_transfer(&ctx.accounts.peer, &params.to)?;
 
         let (_, amount_received_ld, _) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            &ctx.accounts.peer,
         )?;
+        require!(amount_received_ld > 0, OFTError::ZeroAmount);
         require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
 
         // calling endpoint cpi
-        oapp::endpoint_cpi::quote(
+        let mut fee = oapp::endpoint_cpi::quote(
             ctx.accounts.oft_store.endpoint_program,
             ctx.remaining_accounts,
             QuoteParams {
                 sender: ctx.accounts.oft_store.key(),
                 dst_eid: params.dst_eid,
                 receiver: ctx.accounts.peer.peer_address,
-                message: msg_codec::encode(
+                message: msg_codec::encode_versioned(
+                    ctx.accounts.peer.msg_version,
                     params.to,
                     amount_received_ld,
                     Pubkey::default(),
                     &params.compose_msg,
-                ),
+                    &params.extensions,
+                )?,
                 pay_in_lz_token: params.pay_in_lz_token,
-                options: ctx
-                    .accounts
-                    .peer
-                    .enforced_options
-                    .combine_options(&params.compose_msg, &params.options)?,
+                options: ctx.accounts.peer.combine_options(
+                    &ctx.accounts.oft_store,
+                    EnforcedOptions::msg_type(&params.compose_msg),
+                    &params.options,
+                )?,
             },
-        )
+        )?;
+
+        // report the fee the user pays after the subsidy
+        if let (Some(vault), Some(fee_subsidy)) =
+            (&ctx.accounts.subsidy_vault, &ctx.accounts.peer.fee_subsidy)
+        {
+            let limit = vault
+                .remaining_in_epoch(Clock::get()?.epoch)
+                .min(SubsidyVault::spare_lamports(&vault.to_account_info())?);
+            fee.native_fee -= fee_subsidy.subsidy_for_fee(fee.native_fee, limit);
+        }
+        Ok(fee)
     }
 }
 
+/// Rejects sends to a peer without an address or to a zero recipient. Sends of which nothing is
+/// received after the dust and the fees are rejected once the fees are computed.
+pub fn validate_transfer(peer: &PeerConfig, to: &[u8; 32]) -> Result<()> {
+    require!(peer.peer_address != [0; 32], OFTError::PeerNotSet);
+    require!(*to != [0; 32], OFTError::InvalidRecipient);
+    Ok(())
+}
+
 pub fn compute_fee_and_adjust_amount(
     amount_ld: u64,
     oft_store: &OFTStore,
     token_mint: &InterfaceAccount<Mint>,
-    fee_bps: Option<u16>,
+    peer: &PeerConfig,
 ) -> Result<(u64, u64, u64)> {
-    let (amount_sent_ld, amount_received_ld, oft_fee_ld) = if OFTType::Adapter == oft_store.oft_type
-    {
-        let mut amount_received_ld =
-            oft_store.remove_dust(get_post_fee_amount_ld(token_mint, amount_ld)?);
-        let amount_sent_ld = get_pre_fee_amount_ld(token_mint, amount_received_ld)?;
-
-        // remove the oft fee from the amount_received_ld
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_received_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
-        amount_received_ld -= oft_fee_ld;
-        (amount_sent_ld, amount_received_ld, oft_fee_ld)
-    } else {
-        // if it is Native OFT, there is no transfer fee
-        let amount_sent_ld = oft_store.remove_dust(amount_ld);
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_sent_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
-        let amount_received_ld = amount_sent_ld - oft_fee_ld;
-        (amount_sent_ld, amount_received_ld, oft_fee_ld)
-    };
+    compute_fee_and_adjust_amount_at_epoch(
+        amount_ld,
+        oft_store,
+        token_mint,
+        peer,
+        Clock::get()?.epoch,
+    )
+}
+
+pub fn compute_fee_and_adjust_amount_at_epoch(
+    amount_ld: u64,
+    oft_store: &OFTStore,
+    token_mint: &InterfaceAccount<Mint>,
+    peer: &PeerConfig,
+    epoch: u64,
+) -> Result<(u64, u64, u64)> {
+    let fee_bps = peer.fee_bps;
+    let (amount_sent_ld, amount_received_ld, oft_fee_ld) =
+        if OFTType::Adapter == oft_store.pathway_type(peer)? {
+            let mut amount_received_ld = oft_store
+                .remove_dust(get_post_fee_amount_ld_at_epoch(token_mint, amount_ld, epoch)?);
+            let amount_sent_ld = get_pre_fee_amount_ld(token_mint, amount_received_ld, epoch)?;
+
+            // remove the oft fee from the amount_received_ld
+            let oft_fee_ld = oft_store.remove_dust(calculate_fee(
+                amount_received_ld,
+                oft_store.default_fee_bps,
+                fee_bps,
+            ));
+            amount_received_ld -= oft_fee_ld;
+            (amount_sent_ld, amount_received_ld, oft_fee_ld)
+        } else {
+            // if it is a Native pathway, the amount received is burned without a transfer fee. Only the oft fee
+            // is transferred to the escrow, so any transfer fee is borne by the oft fee.
+            let amount_sent_ld = oft_store.remove_dust(amount_ld);
+            let oft_fee_ld = oft_store.remove_dust(calculate_fee(
+                amount_sent_ld,
+                oft_store.default_fee_bps,
+                fee_bps,
+            ));
+            let amount_received_ld = amount_sent_ld - oft_fee_ld;
+            (amount_sent_ld, amount_received_ld, oft_fee_ld)
+        };
     Ok((amount_sent_ld, amount_received_ld, oft_fee_ld))
 }
 
+// the denominator of the oft, referral and subsidy shares in basis points
+pub const BPS_DENOMINATOR: u128 = MAX_FEE_BASIS_POINTS as u128;
+
 fn calculate_fee(pre_fee_amount: u64, default_fee_bps: u16, fee_bps: Option<u16>) -> u64 {
     let final_fee_bps = if let Some(bps) = fee_bps { bps as u128 } else { default_fee_bps as u128 };
     if final_fee_bps == 0 || pre_fee_amount == 0 {
@@ -116,13 +165,21 @@ fn calculate_fee(pre_fee_amount: u64, default_fee_bps: u16, fee_bps: Option<u16>
 }
 
 pub fn get_post_fee_amount_ld(token_mint: &InterfaceAccount<Mint>, amount_ld: u64) -> Result<u64> {
+    get_post_fee_amount_ld_at_epoch(token_mint, amount_ld, Clock::get()?.epoch)
+}
+
+pub fn get_post_fee_amount_ld_at_epoch(
+    token_mint: &InterfaceAccount<Mint>,
+    amount_ld: u64,
+    epoch: u64,
+) -> Result<u64> {
     let token_mint_info = token_mint.to_account_info();
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
     let post_amount_ld =
         if let Ok(transfer_fee_config) = token_mint_ext.get_extension::<TransferFeeConfig>() {
             transfer_fee_config
-                .get_epoch_fee(Clock::get()?.epoch)
+                .get_epoch_fee(epoch)
                 .calculate_post_fee_amount(amount_ld)
                 .ok_or(ProgramError::InvalidArgument)?
         } else {
@@ -131,15 +188,44 @@ pub fn get_post_fee_amount_ld(token_mint: &InterfaceAccount<Mint>, amount_ld: u6
     Ok(post_amount_ld)
 }
 
+// Calculate the Token2022 transfer fee charged on transferring amount_ld
+pub fn get_transfer_fee_ld(
+    token_mint: &InterfaceAccount<Mint>,
+    amount_ld: u64,
+    epoch: u64,
+) -> Result<u64> {
+    Ok(amount_ld - get_post_fee_amount_ld_at_epoch(token_mint, amount_ld, epoch)?)
+}
+
+// Returns the epoch at which the newer Token2022 transfer fee activates, if it is after `epoch`
+pub fn get_transfer_fee_activation_epoch(
+    token_mint: &InterfaceAccount<Mint>,
+    epoch: u64,
+) -> Result<Option<u64>> {
+    let token_mint_info = token_mint.to_account_info();
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
+    let activation_epoch = token_mint_ext
+        .get_extension::<TransferFeeConfig>()
+        .ok()
+        .map(|transfer_fee_config| u64::from(transfer_fee_config.newer_transfer_fee.epoch))
+        .filter(|activation_epoch| *activation_epoch > epoch);
+    Ok(activation_epoch)
+}
+
 // Calculate the amount_sent_ld necessary to receive amount_received_ld
 // Does *not* de-dust any inputs or outputs.
-fn get_pre_fee_amount_ld(token_mint: &InterfaceAccount<Mint>, amount_ld: u64) -> Result<u64> {
+fn get_pre_fee_amount_ld(
+    token_mint: &InterfaceAccount<Mint>,
+    amount_ld: u64,
+    epoch: u64,
+) -> Result<u64> {
     let token_mint_info = token_mint.to_account_info();
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
     let pre_amount_ld =
         if let Ok(transfer_fee) = token_mint_ext.get_extension::<TransferFeeConfig>() {
-            calculate_pre_fee_amount(transfer_fee.get_epoch_fee(Clock::get()?.epoch), amount_ld)
+            calculate_pre_fee_amount(transfer_fee.get_epoch_fee(epoch), amount_ld)
                 .ok_or(ProgramError::InvalidArgument)?
         } else {
             amount_ld
@@ -190,4 +276,5 @@ pub struct QuoteSendParams {
     pub options: Vec<u8>,
     pub compose_msg: Option<Vec<u8>>,
     pub pay_in_lz_token: bool,
+    pub extensions: Option<msg_codec::MsgExtensions>,
 }
@@ -0,0 +1,53 @@
+use crate::*;
+use anchor_lang::system_program;
+
+/// Grows an OFTStore, PeerConfig or LzReceiveTypesAccounts created by an older version of the
+/// program to its current size, so that it can be loaded again after an upgrade. The fields
+/// appended since are zero, i.e. unset. Anyone can pay for the rent.
+#[derive(Accounts)]
+pub struct ReallocAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: an account of this program, identified by its discriminator
+    #[account(mut, owner = ID)]
+    pub account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ReallocAccount<'_> {
+    pub fn apply(ctx: &mut Context<ReallocAccount>) -> Result<()> {
+        let account = ctx.accounts.account.to_account_info();
+        let space = {
+            let data = account.try_borrow_data()?;
+            let discriminator = data.get(..8).ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+            if discriminator == OFTStore::DISCRIMINATOR {
+                8 + OFTStore::INIT_SPACE
+            } else if discriminator == PeerConfig::DISCRIMINATOR {
+                8 + PeerConfig::INIT_SPACE
+            } else if discriminator == LzReceiveTypesAccounts::DISCRIMINATOR {
+                8 + LzReceiveTypesAccounts::INIT_SPACE
+            } else {
+                return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+            }
+        };
+        if account.data_len() >= space {
+            return Ok(());
+        }
+
+        let rent = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+        if rent > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                rent,
+            )?;
+        }
+        account.resize(space)?;
+        Ok(())
+    }
+}
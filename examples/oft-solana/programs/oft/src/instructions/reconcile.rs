@@ -0,0 +1,35 @@
+use crate::*;
+use anchor_spl::token_interface::TokenAccount;
+
+/// Books the escrow balance that is not accounted for as tvl, fees or abandoned amounts, e.g.
+/// direct transfers to the escrow, as accrued fees.
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(address = oft_store.token_escrow)]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl Reconcile<'_> {
+    pub fn apply(ctx: &mut Context<Reconcile>) -> Result<()> {
+        let booked_ld = ctx.accounts.oft_store.booked_escrow_ld();
+        let surplus_ld =
+            (ctx.accounts.token_escrow.amount as u128).saturating_sub(booked_ld) as u64;
+        if surplus_ld > 0 {
+            ctx.accounts.oft_store.add_accrued_fee(surplus_ld)?;
+            emit!(EscrowReconciled {
+                oft_store: ctx.accounts.oft_store.key(),
+                surplus_ld,
+                accrued_fee_ld: ctx.accounts.oft_store.accrued_fee_ld
+            });
+        }
+        Ok(())
+    }
+}
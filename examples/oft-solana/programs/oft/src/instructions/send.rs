@@ -1,13 +1,12 @@
 use crate::*;
-use anchor_spl::token_interface::{
-    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
-};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
 use oapp::endpoint::{instructions::SendParams as EndpointSendParams, MessagingReceipt};
 
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: SendParams)]
 pub struct Send<'info> {
+    #[account(mut)]
     pub signer: Signer<'info>,
     #[account(
         mut,
@@ -47,21 +46,43 @@ pub struct Send<'info> {
     )]
     pub token_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        seeds = [SUBSIDY_VAULT_SEED, oft_store.key().as_ref()],
+        bump = subsidy_vault.bump
+    )]
+    pub subsidy_vault: Option<Account<'info, SubsidyVault>>,
+    pub system_program: Option<Program<'info, System>>,
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, oft_store.key().as_ref(), referrer.referrer.as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
 }
 
-impl Send<'_> {
+impl<'info> Send<'info> {
     pub fn apply(
-        ctx: &mut Context<Send>,
+        ctx: &mut Context<'_, '_, '_, 'info, Send<'info>>,
         params: &SendParams,
     ) -> Result<(MessagingReceipt, OFTReceipt)> {
         require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        if let Some(max_epoch) = params.max_epoch {
+            require!(Clock::get()?.epoch <= max_epoch, OFTError::EpochExceeded);
+        }
+        require!(
+            params.referrer == ctx.accounts.referrer.as_ref().map(|referrer| referrer.referrer),
+            OFTError::InvalidReferrer
+        );
+        validate_transfer(&ctx.accounts.peer, &params.to)?;
 
         let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            &ctx.accounts.peer,
         )?;
+        require!(amount_received_ld > 0, OFTError::ZeroAmount);
         require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
 
         if let Some(rate_limiter) = ctx.accounts.peer.outbound_rate_limiter.as_mut() {
@@ -71,95 +92,207 @@ impl Send<'_> {
             rate_limiter.refill(amount_received_ld)?;
         }
 
-        if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
-            // transfer all tokens to escrow with fee
-            ctx.accounts.oft_store.tvl_ld += amount_received_ld;
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.token_source.to_account_info(),
-                        mint: ctx.accounts.token_mint.to_account_info(),
-                        to: ctx.accounts.token_escrow.to_account_info(),
-                        authority: ctx.accounts.signer.to_account_info(),
-                    },
-                ),
-                amount_sent_ld,
-                ctx.accounts.token_mint.decimals,
-            )?;
-        } else {
-            // Native type
-            // burn
-            token_interface::burn(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    Burn {
-                        mint: ctx.accounts.token_mint.to_account_info(),
-                        from: ctx.accounts.token_source.to_account_info(),
-                        authority: ctx.accounts.signer.to_account_info(),
-                    },
-                ),
-                amount_sent_ld - oft_fee_ld,
-            )?;
+        // remaining accounts: [transfer hook accounts..., endpoint accounts...]
+        let (hook_accounts, endpoint_accounts) = transfer_hook::split_hook_accounts(
+            &ctx.accounts.token_mint.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
 
-            // transfer fee to escrow
-            if oft_fee_ld > 0 {
-                token_interface::transfer_checked(
+        let (transfer_fee_ld, referral_fee_ld) =
+            if ctx.accounts.oft_store.pathway_type(&ctx.accounts.peer)? == OFTType::Adapter {
+                // transfer all tokens to escrow with fee
+                ctx.accounts.oft_store.add_tvl(amount_received_ld)?;
+                let referral_fee_ld = ctx.accounts.book_fee(oft_fee_ld)?;
+                transfer_hook::transfer_checked(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.token_source.to_account_info(),
+                    &ctx.accounts.token_mint.to_account_info(),
+                    &ctx.accounts.token_escrow.to_account_info(),
+                    &ctx.accounts.signer.to_account_info(),
+                    hook_accounts,
+                    amount_sent_ld,
+                    ctx.accounts.token_mint.decimals,
+                    &[],
+                )?;
+                (amount_sent_ld - amount_received_ld - oft_fee_ld, referral_fee_ld)
+            } else {
+                // Native type
+                // burn
+                token_interface::burn(
                     CpiContext::new(
                         ctx.accounts.token_program.to_account_info(),
-                        TransferChecked {
-                            from: ctx.accounts.token_source.to_account_info(),
+                        Burn {
                             mint: ctx.accounts.token_mint.to_account_info(),
-                            to: ctx.accounts.token_escrow.to_account_info(),
+                            from: ctx.accounts.token_source.to_account_info(),
                             authority: ctx.accounts.signer.to_account_info(),
                         },
                     ),
-                    oft_fee_ld,
-                    ctx.accounts.token_mint.decimals,
+                    amount_sent_ld - oft_fee_ld,
                 )?;
-            }
-        }
+
+                // transfer fee to escrow
+                if oft_fee_ld > 0 {
+                    let transfer_fee_ld = get_transfer_fee_ld(
+                        &ctx.accounts.token_mint,
+                        oft_fee_ld,
+                        Clock::get()?.epoch,
+                    )?;
+                    let referral_fee_ld = ctx.accounts.book_fee(oft_fee_ld - transfer_fee_ld)?;
+                    transfer_hook::transfer_checked(
+                        &ctx.accounts.token_program.to_account_info(),
+                        &ctx.accounts.token_source.to_account_info(),
+                        &ctx.accounts.token_mint.to_account_info(),
+                        &ctx.accounts.token_escrow.to_account_info(),
+                        &ctx.accounts.signer.to_account_info(),
+                        hook_accounts,
+                        oft_fee_ld,
+                        ctx.accounts.token_mint.decimals,
+                        &[],
+                    )?;
+                    (transfer_fee_ld, referral_fee_ld)
+                } else {
+                    (0, 0)
+                }
+            };
 
         // send message to endpoint
         require!(
-            ctx.accounts.oft_store.key() == ctx.remaining_accounts[1].key(),
+            ctx.accounts.oft_store.key() == endpoint_accounts[1].key(),
             OFTError::InvalidSender
         );
         let amount_sd = ctx.accounts.oft_store.ld2sd(amount_received_ld);
+        ctx.accounts.peer.record_sent(amount_sd);
+
+        // top up the signer from the subsidy vault so the endpoint send is fully funded
+        let epoch = Clock::get()?.epoch;
+        let subsidy_ld = Self::pre_fund_subsidy(ctx, params.native_fee, epoch)?;
+        let signer_lamports_before = ctx.accounts.signer.lamports();
+
         let msg_receipt = oapp::endpoint_cpi::send(
             ctx.accounts.oft_store.endpoint_program,
             ctx.accounts.oft_store.key(),
-            ctx.remaining_accounts,
+            endpoint_accounts,
             &[OFT_SEED, ctx.accounts.token_escrow.key().as_ref(), &[ctx.accounts.oft_store.bump]],
             EndpointSendParams {
                 dst_eid: params.dst_eid,
                 receiver: ctx.accounts.peer.peer_address,
-                message: msg_codec::encode(
+                message: msg_codec::encode_versioned(
+                    ctx.accounts.peer.msg_version,
                     params.to,
                     amount_sd,
                     ctx.accounts.signer.key(),
                     &params.compose_msg,
-                ),
-                options: ctx
-                    .accounts
-                    .peer
-                    .enforced_options
-                    .combine_options(&params.compose_msg, &params.options)?,
-                native_fee: params.native_fee,
+                    &params.extensions,
+                )?,
+                options: ctx.accounts.peer.combine_options(
+                    &ctx.accounts.oft_store,
+                    EnforcedOptions::msg_type(&params.compose_msg),
+                    &params.options,
+                )?,
+                native_fee: params
+                    .native_fee
+                    .checked_add(subsidy_ld)
+                    .ok_or(OFTError::AmountOverflow)?,
                 lz_token_fee: params.lz_token_fee,
             },
         )?;
 
+        if subsidy_ld > 0 {
+            let native_fee_paid =
+                signer_lamports_before.saturating_sub(ctx.accounts.signer.lamports());
+            Self::settle_subsidy(ctx, subsidy_ld, native_fee_paid, epoch)?;
+        }
+
         emit_cpi!(OFTSent {
             guid: msg_receipt.guid,
             dst_eid: params.dst_eid,
             from: ctx.accounts.token_source.key(),
             amount_sent_ld,
-            amount_received_ld
+            amount_received_ld,
+            referrer: params.referrer,
+            to: params.to,
+            authority: ctx.accounts.signer.key(),
+            oft_fee_ld,
+            referral_fee_ld,
+            transfer_fee_ld,
+            native_fee: msg_receipt.fee.native_fee,
+            lz_token_fee: msg_receipt.fee.lz_token_fee,
+            composed: params.compose_msg.is_some(),
         });
 
         Ok((msg_receipt, OFTReceipt { amount_sent_ld, amount_received_ld }))
     }
+
+    /// Books the oft fee received in the escrow, split between the protocol and the referrer.
+    /// Returns the referrer's share.
+    fn book_fee(&mut self, fee_ld: u64) -> Result<u64> {
+        let referral_fee_ld = match self.referrer.as_mut() {
+            Some(referrer) => {
+                let referral_fee_ld = self.oft_store.referral_share_of(fee_ld);
+                referrer.accrued_fee_ld = referrer
+                    .accrued_fee_ld
+                    .checked_add(referral_fee_ld)
+                    .ok_or(OFTError::AmountOverflow)?;
+                referral_fee_ld
+            },
+            None => 0,
+        };
+        self.oft_store.add_referral_fee(referral_fee_ld)?;
+        self.oft_store.add_accrued_fee(fee_ld - referral_fee_ld)?;
+        Ok(referral_fee_ld)
+    }
+
+    /// Moves the maximum subsidy the user could be entitled to from the vault to the signer.
+    /// The `native_fee` passed by the user is the fee after subsidy, as reported by `quote_send`.
+    fn pre_fund_subsidy(ctx: &mut Context<Send>, native_fee: u64, epoch: u64) -> Result<u64> {
+        let (Some(vault), Some(fee_subsidy)) =
+            (&ctx.accounts.subsidy_vault, &ctx.accounts.peer.fee_subsidy)
+        else {
+            return Ok(0);
+        };
+        let vault_info = vault.to_account_info();
+        let limit = vault.remaining_in_epoch(epoch).min(SubsidyVault::spare_lamports(&vault_info)?);
+        let subsidy = fee_subsidy.subsidy_for_user_fee(native_fee, limit);
+        if subsidy > 0 {
+            **vault_info.try_borrow_mut_lamports()? -= subsidy;
+            **ctx.accounts.signer.to_account_info().try_borrow_mut_lamports()? += subsidy;
+        }
+        Ok(subsidy)
+    }
+
+    /// Books the subsidy on the fee actually charged by the endpoint and returns the excess
+    /// pre-funded lamports from the signer to the vault.
+    fn settle_subsidy(
+        ctx: &mut Context<Send>,
+        pre_funded: u64,
+        native_fee_paid: u64,
+        epoch: u64,
+    ) -> Result<()> {
+        let (Some(vault), Some(fee_subsidy)) =
+            (ctx.accounts.subsidy_vault.as_mut(), &ctx.accounts.peer.fee_subsidy)
+        else {
+            return Ok(());
+        };
+        let subsidy = fee_subsidy.subsidy_for_fee(native_fee_paid, pre_funded);
+        vault.record_spent(epoch, subsidy);
+
+        let refund = pre_funded - subsidy;
+        if refund > 0 {
+            let system_program =
+                ctx.accounts.system_program.as_ref().ok_or(ErrorCode::AccountNotEnoughKeys)?;
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.signer.to_account_info(),
+                        to: vault.to_account_info(),
+                    },
+                ),
+                refund,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -172,4 +305,7 @@ pub struct SendParams {
     pub compose_msg: Option<Vec<u8>>,
     pub native_fee: u64,
     pub lz_token_fee: u64,
+    pub max_epoch: Option<u64>, // guards against a newer Token2022 transfer fee than quoted
+    pub extensions: Option<msg_codec::MsgExtensions>, // only sent to peers on MSG_VERSION_2
+    pub referrer: Option<Pubkey>, // the referrer account must be passed if set
 }
@@ -1,5 +1,8 @@
 use crate::*;
-use oapp::endpoint::instructions::SetDelegateParams;
+use oapp::endpoint::instructions::{
+    SetConfigParams, SetDelegateParams, SetReceiveLibraryParams, SetReceiveLibraryTimeoutParams,
+    SetSendLibraryParams,
+};
 
 #[derive(Accounts)]
 pub struct SetOFTConfig<'info> {
@@ -15,26 +18,82 @@ pub struct SetOFTConfig<'info> {
 
 impl SetOFTConfig<'_> {
     pub fn apply(ctx: &mut Context<SetOFTConfig>, params: &SetOFTConfigParams) -> Result<()> {
+        let oft_store = ctx.accounts.oft_store.key();
+        let oft_store_seed = ctx.accounts.oft_store.token_escrow.key();
+        let seeds: &[&[u8]] =
+            &[OFT_SEED, &oft_store_seed.to_bytes(), &[ctx.accounts.oft_store.bump]];
+        let endpoint_program = ctx.accounts.oft_store.endpoint_program;
         match params.clone() {
             SetOFTConfigParams::Admin(admin) => {
                 ctx.accounts.oft_store.admin = admin;
             },
             SetOFTConfigParams::Delegate(delegate) => {
-                let oft_store_seed = ctx.accounts.oft_store.token_escrow.key();
-                let seeds: &[&[u8]] =
-                    &[OFT_SEED, &oft_store_seed.to_bytes(), &[ctx.accounts.oft_store.bump]];
                 let _ = oapp::endpoint_cpi::set_delegate(
-                    ctx.accounts.oft_store.endpoint_program,
-                    ctx.accounts.oft_store.key(),
+                    endpoint_program,
+                    oft_store,
                     &ctx.remaining_accounts,
                     seeds,
                     SetDelegateParams { delegate },
                 )?;
             },
+            SetOFTConfigParams::SendLibrary { eid, new_lib } => {
+                oapp::endpoint_cpi::set_send_library(
+                    endpoint_program,
+                    oft_store,
+                    &ctx.remaining_accounts,
+                    seeds,
+                    SetSendLibraryParams { sender: oft_store, eid, new_lib },
+                )?;
+            },
+            SetOFTConfigParams::ReceiveLibrary { eid, new_lib, grace_period } => {
+                oapp::endpoint_cpi::set_receive_library(
+                    endpoint_program,
+                    oft_store,
+                    &ctx.remaining_accounts,
+                    seeds,
+                    SetReceiveLibraryParams { receiver: oft_store, eid, new_lib, grace_period },
+                )?;
+            },
+            SetOFTConfigParams::ReceiveLibraryTimeout { eid, lib, expiry } => {
+                oapp::endpoint_cpi::set_receive_library_timeout(
+                    endpoint_program,
+                    oft_store,
+                    &ctx.remaining_accounts,
+                    seeds,
+                    SetReceiveLibraryTimeoutParams { receiver: oft_store, eid, lib, expiry },
+                )?;
+            },
+            SetOFTConfigParams::EndpointConfig { eid, config_type, config } => {
+                oapp::endpoint_cpi::set_config(
+                    endpoint_program,
+                    oft_store,
+                    &ctx.remaining_accounts,
+                    seeds,
+                    SetConfigParams { oapp: oft_store, eid, config_type, config },
+                )?;
+            },
             SetOFTConfigParams::DefaultFee(fee_bps) => {
                 require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
                 ctx.accounts.oft_store.default_fee_bps = fee_bps;
             },
+            SetOFTConfigParams::ReferralFeeShare(share_bps) => {
+                require!(share_bps <= MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
+                ctx.accounts.oft_store.referral_fee_share_bps = share_bps;
+            },
+            SetOFTConfigParams::DefaultEnforcedOptions { msg_type, options } => {
+                EnforcedOptionsEntry::set(
+                    &mut ctx.accounts.oft_store.default_enforced_options,
+                    msg_type,
+                    options,
+                )?;
+            },
+            SetOFTConfigParams::MaxSupply(max_supply_ld) => {
+                require!(
+                    ctx.accounts.oft_store.oft_type != OFTType::Adapter,
+                    OFTError::InvalidOFTType
+                );
+                ctx.accounts.oft_store.max_supply_ld = max_supply_ld;
+            },
             SetOFTConfigParams::Paused(paused) => {
                 ctx.accounts.oft_store.paused = paused;
             },
@@ -45,6 +104,7 @@ impl SetOFTConfig<'_> {
                 ctx.accounts.oft_store.unpauser = unpauser;
             },
         }
+        emit!(OFTConfigSet { oft_store, params: params.clone() });
         Ok(())
     }
 }
@@ -57,4 +117,13 @@ pub enum SetOFTConfigParams {
     Paused(bool),
     Pauser(Option<Pubkey>),
     Unpauser(Option<Pubkey>),
+    // variants are encoded by index, so new variants are only appended
+    MaxSupply(Option<u64>), // only for Native and Hybrid
+    ReferralFeeShare(u16),  // share of the oft fee in bps
+    // endpoint library and message library (ULN, executor) configuration of the OFT store
+    SendLibrary { eid: u32, new_lib: Pubkey },
+    ReceiveLibrary { eid: u32, new_lib: Pubkey, grace_period: u64 },
+    ReceiveLibraryTimeout { eid: u32, lib: Pubkey, expiry: u64 },
+    EndpointConfig { eid: u32, config_type: u32, config: Vec<u8> },
+    DefaultEnforcedOptions { msg_type: u16, options: Vec<u8> }, // empty options remove the default
 }
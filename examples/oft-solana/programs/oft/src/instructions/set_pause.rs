@@ -17,6 +17,11 @@ pub struct SetPause<'info> {
 impl SetPause<'_> {
     pub fn apply(ctx: &mut Context<SetPause>, params: &SetPauseParams) -> Result<()> {
         ctx.accounts.oft_store.paused = params.paused;
+        emit!(PauseSet {
+            oft_store: ctx.accounts.oft_store.key(),
+            signer: ctx.accounts.signer.key(),
+            paused: params.paused,
+        });
         Ok(())
     }
 }
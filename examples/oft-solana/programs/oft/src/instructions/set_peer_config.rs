@@ -28,18 +28,80 @@ impl SetPeerConfig<'_> {
             PeerConfigParam::PeerAddress(peer_address) => {
                 ctx.accounts.peer.peer_address = peer_address;
             },
+            PeerConfigParam::SecondarySender(secondary_sender) => {
+                // the endpoint tracks the nonces per sender, so only one sender can be ordered
+                require!(
+                    secondary_sender.is_none() || !ctx.accounts.peer.ordered_inbound,
+                    OFTError::OrderedWithSecondarySender
+                );
+                ctx.accounts.peer.secondary_sender = secondary_sender;
+            },
             PeerConfigParam::FeeBps(fee_bps) => {
                 if let Some(fee_bps) = fee_bps {
                     require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
                 }
                 ctx.accounts.peer.fee_bps = fee_bps;
             },
+            PeerConfigParam::FeeSubsidy(fee_subsidy) => {
+                if let Some(fee_subsidy) = &fee_subsidy {
+                    require!(fee_subsidy.subsidy_bps <= MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
+                }
+                ctx.accounts.peer.fee_subsidy = fee_subsidy;
+            },
+            PeerConfigParam::PathwayType(pathway_type) => {
+                require!(pathway_type != Some(OFTType::Hybrid), OFTError::InvalidPathwayType);
+                // the outstanding amount was minted or locked on the current pathway, so it must
+                // come back before the tokens are released the other way
+                require!(
+                    pathway_type == ctx.accounts.peer.pathway_type
+                        || ctx.accounts.peer.outstanding_sd() == 0,
+                    OFTError::PeerInFlight
+                );
+                ctx.accounts.peer.pathway_type = pathway_type;
+            },
+            PeerConfigParam::CreditLimit(credit_limit_sd) => {
+                ctx.accounts.peer.credit_limit_sd = credit_limit_sd;
+            },
+            PeerConfigParam::OrderedInbound { ordered, inbound_nonce } => {
+                require!(
+                    !ordered || ctx.accounts.peer.secondary_sender.is_none(),
+                    OFTError::OrderedWithSecondarySender
+                );
+                ctx.accounts.peer.ordered_inbound = ordered;
+                ctx.accounts.peer.inbound_nonce = inbound_nonce;
+            },
+            PeerConfigParam::MsgVersion(msg_version) => {
+                require!(
+                    msg_version == msg_codec::MSG_VERSION_1
+                        || msg_version == msg_codec::MSG_VERSION_2,
+                    OFTError::InvalidMessage
+                );
+                ctx.accounts.peer.msg_version = msg_version;
+            },
             PeerConfigParam::EnforcedOptions { send, send_and_call } => {
                 oapp::options::assert_type_3(&send)?;
                 ctx.accounts.peer.enforced_options.send = send;
                 oapp::options::assert_type_3(&send_and_call)?;
                 ctx.accounts.peer.enforced_options.send_and_call = send_and_call;
             },
+            PeerConfigParam::EnforcedOptionsByType { msg_type, options } => match msg_type {
+                MSG_TYPE_SEND | MSG_TYPE_SEND_AND_CALL => {
+                    oapp::options::assert_type_3(&options)?;
+                    if msg_type == MSG_TYPE_SEND {
+                        ctx.accounts.peer.enforced_options.send = options;
+                    } else {
+                        ctx.accounts.peer.enforced_options.send_and_call = options;
+                    }
+                },
+                _ => EnforcedOptionsEntry::set(
+                    &mut ctx.accounts.peer.enforced_options_by_type,
+                    msg_type,
+                    options,
+                )?,
+            },
+            PeerConfigParam::ExecutorOptionsPolicy(policy) => {
+                ctx.accounts.peer.executor_options_policy = policy;
+            },
             PeerConfigParam::OutboundRateLimit(rate_limit_params) => {
                 Self::update_rate_limiter(
                     &mut ctx.accounts.peer.outbound_rate_limiter,
@@ -54,6 +116,11 @@ impl SetPeerConfig<'_> {
             },
         }
         ctx.accounts.peer.bump = ctx.bumps.peer;
+        emit!(PeerConfigSet {
+            oft_store: ctx.accounts.oft_store.key(),
+            remote_eid: params.remote_eid,
+            config: params.config.clone(),
+        });
         Ok(())
     }
 
@@ -90,6 +157,16 @@ pub enum PeerConfigParam {
     EnforcedOptions { send: Vec<u8>, send_and_call: Vec<u8> },
     OutboundRateLimit(Option<RateLimitParams>),
     InboundRateLimit(Option<RateLimitParams>),
+    // variants are encoded by index, so new variants are only appended
+    FeeSubsidy(Option<FeeSubsidy>),
+    PathwayType(Option<OFTType>),
+    CreditLimit(Option<u64>),
+    MsgVersion(u8),
+    OrderedInbound { ordered: bool, inbound_nonce: u64 }, // inbound_nonce is the last nonce received
+    // empty options unset the peer's options of msg_type, falling back to the store default
+    EnforcedOptionsByType { msg_type: u16, options: Vec<u8> },
+    ExecutorOptionsPolicy(Option<ExecutorOptionsPolicy>),
+    SecondarySender(Option<SecondarySender>), // accepted on receive until its expiry
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -0,0 +1,39 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetSubsidyVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SubsidyVault::INIT_SPACE,
+        seeds = [SUBSIDY_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub subsidy_vault: Account<'info, SubsidyVault>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetSubsidyVault<'_> {
+    pub fn apply(ctx: &mut Context<SetSubsidyVault>, params: &SetSubsidyVaultParams) -> Result<()> {
+        ctx.accounts.subsidy_vault.max_per_epoch = params.max_per_epoch;
+        ctx.accounts.subsidy_vault.bump = ctx.bumps.subsidy_vault;
+        emit!(SubsidyVaultSet {
+            oft_store: ctx.accounts.oft_store.key(),
+            max_per_epoch: params.max_per_epoch,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetSubsidyVaultParams {
+    pub max_per_epoch: u64,
+}
@@ -0,0 +1,192 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+/// Runs the checks of `send` without moving funds and returns every failing check.
+#[derive(Accounts)]
+#[instruction(params: SendParams)]
+pub struct ValidateSend<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    /// CHECK: the peer config, which may not be initialized
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub peer: UncheckedAccount<'info>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = token_mint)]
+    pub token_source: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [REFERRER_SEED, oft_store.key().as_ref(), referrer.referrer.as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+}
+
+// the default maxMessageSize of the LayerZero executor
+pub const MAX_MESSAGE_LEN: usize = 10_000;
+
+impl ValidateSend<'_> {
+    pub fn apply(ctx: &Context<ValidateSend>, params: &SendParams) -> Result<Vec<SendViolation>> {
+        let oft_store = &ctx.accounts.oft_store;
+        let mut violations = vec![];
+        let mut violate = |check: SendCheck, expected: u64, actual: u64| {
+            violations.push(SendViolation { check, expected, actual });
+        };
+
+        if oft_store.paused {
+            violate(SendCheck::Paused, 0, 1);
+        }
+        if let Some(max_epoch) = params.max_epoch {
+            let epoch = Clock::get()?.epoch;
+            if epoch > max_epoch {
+                violate(SendCheck::Epoch, max_epoch, epoch);
+            }
+        }
+        let referrer = ctx.accounts.referrer.as_ref().map(|referrer| referrer.referrer);
+        if params.referrer != referrer {
+            violate(
+                SendCheck::Referrer,
+                params.referrer.is_some() as u64,
+                referrer.is_some() as u64,
+            );
+        }
+
+        let peer_info = ctx.accounts.peer.to_account_info();
+        let peer = if *peer_info.owner == ID {
+            PeerConfig::try_deserialize(&mut &peer_info.try_borrow_data()?[..]).ok()
+        } else {
+            None
+        };
+        let Some(peer) = peer else {
+            violate(SendCheck::PeerNotInitialized, 1, 0);
+            return Ok(violations);
+        };
+        if peer.peer_address == [0; 32] {
+            violate(SendCheck::PeerAddressNotSet, 1, 0);
+        }
+        if params.to == [0; 32] {
+            violate(SendCheck::RecipientNotSet, 1, 0);
+        }
+        if oft_store.pathway_type(&peer).is_err() {
+            violate(SendCheck::PathwayTypeNotSet, 1, 0);
+            return Ok(violations);
+        }
+
+        let message_len = msg_codec::encode_versioned(
+            peer.msg_version,
+            params.to,
+            oft_store.ld2sd(params.amount_ld),
+            Pubkey::default(),
+            &params.compose_msg,
+            &params.extensions,
+        )
+        .map_or(usize::MAX, |message| message.len());
+        if message_len > MAX_MESSAGE_LEN {
+            violate(SendCheck::MessageSize, MAX_MESSAGE_LEN as u64, message_len as u64);
+        }
+
+        let msg_type = EnforcedOptions::msg_type(&params.compose_msg);
+        let enforced_options = peer.get_enforced_options(oft_store, msg_type);
+        let executor_options = oapp::options::combine_options(enforced_options, &params.options)
+            .ok()
+            .and_then(|options| options::decode_executor_options(&options).ok());
+        match (executor_options, &peer.executor_options_policy) {
+            (None, _) => violate(SendCheck::Options, 1, 0),
+            (Some(executor_options), Some(policy)) => {
+                let lz_receive_gas = executor_options.lz_receive_gas();
+                if lz_receive_gas < policy.min_lz_receive_gas {
+                    violate(
+                        SendCheck::LzReceiveGas,
+                        saturate(policy.min_lz_receive_gas),
+                        saturate(lz_receive_gas),
+                    );
+                }
+                let native_drop = executor_options.total_native_drop();
+                if native_drop > policy.max_native_drop {
+                    violate(
+                        SendCheck::NativeDrop,
+                        saturate(policy.max_native_drop),
+                        saturate(native_drop),
+                    );
+                }
+                let compose_gas = executor_options.max_lz_compose_gas();
+                if compose_gas > policy.max_compose_gas {
+                    violate(
+                        SendCheck::ComposeGas,
+                        saturate(policy.max_compose_gas),
+                        saturate(compose_gas),
+                    );
+                }
+            },
+            (Some(_), None) => {},
+        }
+
+        let Ok((amount_sent_ld, amount_received_ld, _)) = compute_fee_and_adjust_amount(
+            params.amount_ld,
+            oft_store,
+            &ctx.accounts.token_mint,
+            &peer,
+        ) else {
+            violate(SendCheck::Fee, 1, 0);
+            return Ok(violations);
+        };
+        if amount_received_ld == 0 {
+            violate(SendCheck::AmountTooSmall, oft_store.ld2sd_rate, amount_received_ld);
+        }
+        if amount_received_ld < params.min_amount_ld {
+            violate(SendCheck::Slippage, params.min_amount_ld, amount_received_ld);
+        }
+        if let Some(rate_limiter) = peer.outbound_rate_limiter.as_ref() {
+            let available = rate_limiter.tokens_at(current_timestamp()?);
+            if amount_received_ld > available {
+                violate(SendCheck::RateLimit, available, amount_received_ld);
+            }
+        }
+        if ctx.accounts.token_source.amount < amount_sent_ld {
+            violate(SendCheck::TokenBalance, amount_sent_ld, ctx.accounts.token_source.amount);
+        }
+        Ok(violations)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendViolation {
+    pub check: SendCheck,
+    pub expected: u64, // 1 or 0 for boolean checks
+    pub actual: u64,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum SendCheck {
+    Paused,
+    PeerNotInitialized,
+    PeerAddressNotSet,
+    RecipientNotSet,
+    PathwayTypeNotSet,
+    AmountTooSmall,
+    Slippage,
+    RateLimit,
+    MessageSize, // the message with the compose msg and extensions, usize::MAX if not encodable
+    TokenBalance,
+    Epoch,        // the max_epoch of the params and the current epoch
+    Referrer,     // whether the params and the accounts have a referrer, or a different one
+    Options,      // the enforced and extra options can not be combined or decoded
+    LzReceiveGas, // see `ExecutorOptionsPolicy`
+    NativeDrop,
+    ComposeGas,
+    Fee, // the fee and amounts can not be computed
+}
+
+// policy values are u128, as in the options
+fn saturate(value: u128) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
@@ -1,10 +1,11 @@
 use crate::*;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct WithdrawFee<'info> {
     pub admin: Signer<'info>,
     #[account(
+        mut,
         seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
         bump = oft_store.bump,
         has_one = admin @OFTError::Unauthorized
@@ -32,31 +33,43 @@ pub struct WithdrawFee<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl WithdrawFee<'_> {
-    pub fn apply(ctx: &mut Context<WithdrawFee>, params: &WithdrawFeeParams) -> Result<()> {
-        require!(
-            ctx.accounts.token_escrow.amount - ctx.accounts.oft_store.tvl_ld >= params.fee_ld,
-            OFTError::InvalidFee
-        );
+impl<'info> WithdrawFee<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, WithdrawFee<'info>>,
+        params: &WithdrawFeeParams,
+    ) -> Result<()> {
+        ctx.accounts.oft_store.accrued_fee_ld = ctx
+            .accounts
+            .oft_store
+            .accrued_fee_ld
+            .checked_sub(params.fee_ld)
+            .ok_or(OFTError::InvalidFee)?;
         let seeds: &[&[u8]] = &[
             OFT_SEED,
             &ctx.accounts.token_escrow.key().to_bytes(),
             &[ctx.accounts.oft_store.bump],
         ];
-        token_interface::transfer_checked(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.token_escrow.to_account_info(),
-                    mint: ctx.accounts.token_mint.to_account_info(),
-                    to: ctx.accounts.token_dest.to_account_info(),
-                    authority: ctx.accounts.oft_store.to_account_info(),
-                },
-            )
-            .with_signer(&[&seeds]),
+        // remaining accounts: [transfer hook accounts...]
+        let (hook_accounts, _) = transfer_hook::split_hook_accounts(
+            &ctx.accounts.token_mint.to_account_info(),
+            ctx.remaining_accounts,
+        )?;
+        transfer_hook::transfer_checked(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_escrow.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.token_dest.to_account_info(),
+            &ctx.accounts.oft_store.to_account_info(),
+            hook_accounts,
             params.fee_ld,
             ctx.accounts.token_mint.decimals,
+            &[&seeds],
         )?;
+        emit!(FeeWithdrawn {
+            oft_store: ctx.accounts.oft_store.key(),
+            token_dest: ctx.accounts.token_dest.key(),
+            fee_ld: params.fee_ld,
+        });
         Ok(())
     }
 }
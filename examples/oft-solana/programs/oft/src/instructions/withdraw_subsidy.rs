@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct WithdrawSubsidy<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [SUBSIDY_VAULT_SEED, oft_store.key().as_ref()],
+        bump = subsidy_vault.bump
+    )]
+    pub subsidy_vault: Account<'info, SubsidyVault>,
+    /// CHECK: the account to receive the withdrawn lamports
+    #[account(mut)]
+    pub receiver: AccountInfo<'info>,
+}
+
+impl WithdrawSubsidy<'_> {
+    pub fn apply(ctx: &mut Context<WithdrawSubsidy>, params: &WithdrawSubsidyParams) -> Result<()> {
+        let vault_info = ctx.accounts.subsidy_vault.to_account_info();
+        require!(
+            SubsidyVault::spare_lamports(&vault_info)? >= params.lamports,
+            OFTError::InsufficientSubsidy
+        );
+        **vault_info.try_borrow_mut_lamports()? -= params.lamports;
+        **ctx.accounts.receiver.try_borrow_mut_lamports()? += params.lamports;
+        emit!(SubsidyWithdrawn {
+            oft_store: ctx.accounts.oft_store.key(),
+            receiver: ctx.accounts.receiver.key(),
+            lamports: params.lamports,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawSubsidyParams {
+    pub lamports: u64,
+}
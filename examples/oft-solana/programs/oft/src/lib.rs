@@ -6,6 +6,9 @@ pub mod events;
 pub mod instructions;
 pub mod msg_codec;
 pub mod state;
+pub mod transfer_hook;
+
+pub use lz_options as options;
 
 use errors::*;
 use events::*;
@@ -25,6 +28,8 @@ declare_id!(Pubkey::new_from_array(program_id_from_env!(
 pub const OFT_SEED: &[u8] = b"OFT";
 pub const PEER_SEED: &[u8] = b"Peer";
 pub const ENFORCED_OPTIONS_SEED: &[u8] = b"EnforcedOptions";
+pub const SUBSIDY_VAULT_SEED: &[u8] = b"SubsidyVault";
+pub const REFERRER_SEED: &[u8] = b"Referrer";
 pub const LZ_RECEIVE_TYPES_SEED: &[u8] = oapp::LZ_RECEIVE_TYPES_SEED;
 
 #[program]
@@ -32,7 +37,7 @@ pub mod oft {
     use super::*;
 
     pub fn oft_version(_ctx: Context<OFTVersion>) -> Result<Version> {
-        Ok(Version { interface: 2, message: 1 })
+        Ok(Version { interface: 3, message: 1 })
     }
 
     pub fn init_oft(mut ctx: Context<InitOFT>, params: InitOFTParams) -> Result<()> {
@@ -54,14 +59,43 @@ pub mod oft {
         SetPeerConfig::apply(&mut ctx, &params)
     }
 
+    pub fn close_peer(mut ctx: Context<ClosePeer>, params: ClosePeerParams) -> Result<()> {
+        ClosePeer::apply(&mut ctx, &params)
+    }
+
+    pub fn manage_packet(mut ctx: Context<ManagePacket>, params: ManagePacketParams) -> Result<()> {
+        ManagePacket::apply(&mut ctx, &params)
+    }
+
     pub fn set_pause(mut ctx: Context<SetPause>, params: SetPauseParams) -> Result<()> {
         SetPause::apply(&mut ctx, &params)
     }
 
-    pub fn withdraw_fee(mut ctx: Context<WithdrawFee>, params: WithdrawFeeParams) -> Result<()> {
+    pub fn withdraw_fee<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, WithdrawFee<'info>>,
+        params: WithdrawFeeParams,
+    ) -> Result<()> {
         WithdrawFee::apply(&mut ctx, &params)
     }
 
+    pub fn reconcile(mut ctx: Context<Reconcile>) -> Result<()> {
+        Reconcile::apply(&mut ctx)
+    }
+
+    pub fn set_subsidy_vault(
+        mut ctx: Context<SetSubsidyVault>,
+        params: SetSubsidyVaultParams,
+    ) -> Result<()> {
+        SetSubsidyVault::apply(&mut ctx, &params)
+    }
+
+    pub fn withdraw_subsidy(
+        mut ctx: Context<WithdrawSubsidy>,
+        params: WithdrawSubsidyParams,
+    ) -> Result<()> {
+        WithdrawSubsidy::apply(&mut ctx, &params)
+    }
+
     // ============================== Public ==============================
 
     pub fn quote_oft(ctx: Context<QuoteOFT>, params: QuoteOFTParams) -> Result<QuoteOFTResult> {
@@ -72,17 +106,71 @@ pub mod oft {
         QuoteSend::apply(&ctx, &params)
     }
 
-    pub fn send(
-        mut ctx: Context<Send>,
+    pub fn assert_invariants(
+        ctx: Context<AssertInvariants>,
+        params: AssertInvariantsParams,
+    ) -> Result<InvariantReport> {
+        AssertInvariants::apply(&ctx, &params)
+    }
+
+    pub fn validate_send(
+        ctx: Context<ValidateSend>,
+        params: SendParams,
+    ) -> Result<Vec<SendViolation>> {
+        ValidateSend::apply(&ctx, &params)
+    }
+
+    pub fn next_nonce(ctx: Context<NextNonce>, params: NextNonceParams) -> Result<u64> {
+        NextNonce::apply(&ctx, &params)
+    }
+
+    pub fn get_peer_state(
+        ctx: Context<GetPeerState>,
+        params: GetPeerStateParams,
+    ) -> Result<PeerState> {
+        GetPeerState::apply(&ctx, &params)
+    }
+
+    pub fn get_peer_accounting(
+        ctx: Context<GetPeerAccounting>,
+        params: GetPeerAccountingParams,
+    ) -> Result<PeerAccounting> {
+        GetPeerAccounting::apply(&ctx, &params)
+    }
+
+    pub fn send<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, Send<'info>>,
         params: SendParams,
     ) -> Result<(MessagingReceipt, OFTReceipt)> {
         Send::apply(&mut ctx, &params)
     }
 
-    pub fn lz_receive(mut ctx: Context<LzReceive>, params: LzReceiveParams) -> Result<()> {
+    pub fn lz_receive<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, LzReceive<'info>>,
+        params: LzReceiveParams,
+    ) -> Result<()> {
         LzReceive::apply(&mut ctx, &params)
     }
 
+    pub fn realloc_account(mut ctx: Context<ReallocAccount>) -> Result<()> {
+        ReallocAccount::apply(&mut ctx)
+    }
+
+    pub fn harvest_withheld_fees(mut ctx: Context<HarvestWithheldFees>) -> Result<()> {
+        HarvestWithheldFees::apply(&mut ctx)
+    }
+
+    pub fn init_referrer(mut ctx: Context<InitReferrer>, params: InitReferrerParams) -> Result<()> {
+        InitReferrer::apply(&mut ctx, &params)
+    }
+
+    pub fn claim_referral_fee<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, ClaimReferralFee<'info>>,
+        params: ClaimReferralFeeParams,
+    ) -> Result<()> {
+        ClaimReferralFee::apply(&mut ctx, &params)
+    }
+
     pub fn lz_receive_types(
         ctx: Context<LzReceiveTypes>,
         params: LzReceiveParams,
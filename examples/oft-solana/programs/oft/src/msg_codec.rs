@@ -3,6 +3,80 @@ use crate::*;
 const SEND_TO_OFFSET: usize = 0;
 const SEND_AMOUNT_SD_OFFSET: usize = 32;
 const COMPOSE_MSG_OFFSET: usize = 40;
+const COMPOSE_MSG_MIN_LEN: usize = 72; // 32 + 8 + 32 (compose from)
+const EXTENSIONS_TRAILER_LEN: usize = 11; // [extensionsLen: u16][magic: 8][version: u8]
+
+pub const MSG_VERSION_1: u8 = 1;
+// the v1 message followed by [extensions][extensionsLen: u16][magic][version: u8], where the
+// extensions are [type: u8][len: u16][value] entries. peers are sent v2 messages if negotiated, see
+// `PeerConfig::msg_version`. the trailer of a received message is only parsed if the peer is on v2,
+// since the compose msg of a v1 message may end with the same bytes.
+pub const MSG_VERSION_2: u8 = 2;
+pub const MSG_V2_MAGIC: [u8; 8] = *b"LZOFTEXT";
+
+pub const EXTENSION_MEMO: u8 = 1;
+pub const EXTENSION_REFERRAL_ID: u8 = 2;
+pub const EXTENSION_ORIGIN_TIMESTAMP: u8 = 3;
+pub const EXTENSION_COMPLIANCE_TAG: u8 = 4;
+
+/// Optional transfer metadata carried by v2 messages. Unknown extension types are ignored.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct MsgExtensions {
+    pub memo: Option<Vec<u8>>,
+    pub referral_id: Option<[u8; 32]>,
+    pub origin_timestamp: Option<u64>,
+    pub compliance_tag: Option<Vec<u8>>,
+}
+
+impl MsgExtensions {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut encoded = Vec::new();
+        let mut push = |ext_type: u8, value: &[u8]| -> Result<()> {
+            let len = u16::try_from(value.len()).map_err(|_| OFTError::InvalidMessage)?;
+            encoded.push(ext_type);
+            encoded.extend_from_slice(&len.to_be_bytes());
+            encoded.extend_from_slice(value);
+            Ok(())
+        };
+        if let Some(memo) = &self.memo {
+            push(EXTENSION_MEMO, memo)?;
+        }
+        if let Some(referral_id) = &self.referral_id {
+            push(EXTENSION_REFERRAL_ID, referral_id)?;
+        }
+        if let Some(origin_timestamp) = self.origin_timestamp {
+            push(EXTENSION_ORIGIN_TIMESTAMP, &origin_timestamp.to_be_bytes())?;
+        }
+        if let Some(compliance_tag) = &self.compliance_tag {
+            push(EXTENSION_COMPLIANCE_TAG, compliance_tag)?;
+        }
+        Ok(encoded)
+    }
+
+    pub fn decode(mut data: &[u8]) -> Result<Self> {
+        let mut extensions = Self::default();
+        while !data.is_empty() {
+            require!(data.len() >= 3, OFTError::InvalidMessage);
+            let len = u16::from_be_bytes([data[1], data[2]]) as usize;
+            let value = data.get(3..3 + len).ok_or(OFTError::InvalidMessage)?;
+            match data[0] {
+                EXTENSION_MEMO => extensions.memo = Some(value.to_vec()),
+                EXTENSION_REFERRAL_ID => {
+                    extensions.referral_id =
+                        Some(value.try_into().map_err(|_| OFTError::InvalidMessage)?);
+                },
+                EXTENSION_ORIGIN_TIMESTAMP => {
+                    let timestamp = value.try_into().map_err(|_| OFTError::InvalidMessage)?;
+                    extensions.origin_timestamp = Some(u64::from_be_bytes(timestamp));
+                },
+                EXTENSION_COMPLIANCE_TAG => extensions.compliance_tag = Some(value.to_vec()),
+                _ => {}, // unknown extensions are ignored
+            }
+            data = &data[3 + len..];
+        }
+        Ok(extensions)
+    }
+}
 
 pub fn encode(
     send_to: [u8; 32],
@@ -25,22 +99,84 @@ pub fn encode(
     }
 }
 
-pub fn send_to(message: &[u8]) -> [u8; 32] {
+/// Encodes a message in the version negotiated with the peer. v1 messages can not carry extensions.
+pub fn encode_versioned(
+    version: u8,
+    send_to: [u8; 32],
+    amount_sd: u64,
+    sender: Pubkey,
+    compose_msg: &Option<Vec<u8>>,
+    extensions: &Option<MsgExtensions>,
+) -> Result<Vec<u8>> {
+    let mut encoded = encode(send_to, amount_sd, sender, compose_msg);
+    if version < MSG_VERSION_2 {
+        require!(extensions.is_none(), OFTError::InvalidMessage);
+        return Ok(encoded);
+    }
+    let extensions = extensions.clone().unwrap_or_default().encode()?;
+    let extensions_len = u16::try_from(extensions.len()).map_err(|_| OFTError::InvalidMessage)?;
+    encoded.extend_from_slice(&extensions);
+    encoded.extend_from_slice(&extensions_len.to_be_bytes());
+    encoded.extend_from_slice(&MSG_V2_MAGIC);
+    encoded.push(MSG_VERSION_2);
+    Ok(encoded)
+}
+
+/// Splits a message from a peer on `version` into the v1 message and its extensions. The
+/// extensions are empty for peers on v1, and for v1 messages from peers on v2.
+pub fn split_extensions(version: u8, message: &[u8]) -> Result<(&[u8], MsgExtensions)> {
+    if version < MSG_VERSION_2 || !has_extensions_trailer(message) {
+        validate(message)?;
+        return Ok((message, MsgExtensions::default()));
+    }
+    let (rest, trailer) = message.split_at(message.len() - EXTENSIONS_TRAILER_LEN);
+    let extensions_len = u16::from_be_bytes([trailer[0], trailer[1]]) as usize;
+    require!(rest.len() >= extensions_len, OFTError::InvalidMessage);
+    let (body, extensions) = rest.split_at(rest.len() - extensions_len);
+    validate(body)?;
+    Ok((body, MsgExtensions::decode(extensions)?))
+}
+
+fn has_extensions_trailer(message: &[u8]) -> bool {
+    match message.len().checked_sub(EXTENSIONS_TRAILER_LEN) {
+        Some(start) if start >= COMPOSE_MSG_OFFSET => {
+            let trailer = &message[start..];
+            trailer[2..10] == MSG_V2_MAGIC && trailer[10] == MSG_VERSION_2
+        },
+        _ => false,
+    }
+}
+
+/// The message is either [sendTo][amountSD] or [sendTo][amountSD][composeFrom][composeMsg].
+/// The layout is shared with the OFTs on other chains, so it is validated by its length.
+pub fn validate(message: &[u8]) -> Result<()> {
+    require!(
+        message.len() == COMPOSE_MSG_OFFSET || message.len() >= COMPOSE_MSG_MIN_LEN,
+        OFTError::InvalidMessage
+    );
+    Ok(())
+}
+
+// `send_to` and `amount_sd` only need the fixed prefix, which v1 and v2 messages share
+pub fn send_to(message: &[u8]) -> Result<[u8; 32]> {
+    require!(message.len() >= COMPOSE_MSG_OFFSET, OFTError::InvalidMessage);
     let mut send_to = [0; 32];
     send_to.copy_from_slice(&message[SEND_TO_OFFSET..SEND_AMOUNT_SD_OFFSET]);
-    send_to
+    Ok(send_to)
 }
 
-pub fn amount_sd(message: &[u8]) -> u64 {
+pub fn amount_sd(message: &[u8]) -> Result<u64> {
+    require!(message.len() >= COMPOSE_MSG_OFFSET, OFTError::InvalidMessage);
     let mut amount_sd_bytes = [0; 8];
     amount_sd_bytes.copy_from_slice(&message[SEND_AMOUNT_SD_OFFSET..COMPOSE_MSG_OFFSET]);
-    u64::from_be_bytes(amount_sd_bytes)
+    Ok(u64::from_be_bytes(amount_sd_bytes))
 }
 
-pub fn compose_msg(message: &[u8]) -> Option<Vec<u8>> {
+pub fn compose_msg(message: &[u8]) -> Result<Option<Vec<u8>>> {
+    validate(message)?;
     if message.len() > COMPOSE_MSG_OFFSET {
-        Some(message[COMPOSE_MSG_OFFSET..].to_vec())
+        Ok(Some(message[COMPOSE_MSG_OFFSET..].to_vec()))
     } else {
-        None
+        Ok(None)
     }
 }
@@ -1,5 +1,9 @@
 pub mod oft;
 pub mod peer_config;
+pub mod referrer;
+pub mod subsidy_vault;
 
 pub use oft::*;
 pub use peer_config::*;
+pub use referrer::*;
+pub use subsidy_vault::*;
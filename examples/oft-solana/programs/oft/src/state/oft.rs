@@ -7,32 +7,117 @@ pub struct OFTStore {
     pub oft_type: OFTType,
     pub ld2sd_rate: u64,
     pub token_mint: Pubkey,
-    pub token_escrow: Pubkey, // this account is used to hold TVL and fees
+    pub token_escrow: Pubkey, // this account is used to hold TVL and fees (accrued_fee_ld)
     pub endpoint_program: Pubkey,
     pub bump: u8,
     // mutable
-    pub tvl_ld: u64, // total value locked. if oft_type is Native, it is always 0.
+    // total value locked. if oft_type is Native, it is always 0.
+    // if oft_type is Hybrid, only Adapter pathways are tracked.
+    pub tvl_ld: u64,
     // configurable
     pub admin: Pubkey,
     pub default_fee_bps: u16,
     pub paused: bool,
     pub pauser: Option<Pubkey>,
     pub unpauser: Option<Pubkey>,
+    // immutable, risky mint extensions recorded at init
+    pub mint_flags: u8,
+    // configurable, caps the token supply minted by Native pathways
+    pub max_supply_ld: Option<u64>,
+    // mutable, oft fees held in the escrow that the admin can withdraw
+    pub accrued_fee_ld: u64,
+    // configurable, the share of the oft fee paid to the referrer of a send
+    pub referral_fee_share_bps: u16,
+    // mutable, oft fees held in the escrow for referrers, see `Referrer`
+    pub referral_fee_ld: u64,
+    // configurable, enforced options by msg type used when the peer has none
+    #[max_len(ENFORCED_OPTIONS_MAX_ENTRIES)]
+    pub default_enforced_options: Vec<EnforcedOptionsEntry>,
+    // mutable, escrow released from the tvl by burned or cleared packets, never withdrawn as fees
+    pub abandoned_ld: u64,
 }
 
+pub const MINT_FLAG_PERMANENT_DELEGATE: u8 = 1 << 0;
+pub const MINT_FLAG_CLOSE_AUTHORITY: u8 = 1 << 1;
+pub const MINT_FLAG_TRANSFER_HOOK: u8 = 1 << 2;
+
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum OFTType {
     Native,
     Adapter,
+    Hybrid, // Native or Adapter selected per peer
 }
 
 impl OFTStore {
+    /// The token handling of the pathway to `peer`. Hybrid stores select it per peer.
+    pub fn pathway_type(&self, peer: &PeerConfig) -> Result<OFTType> {
+        match self.oft_type {
+            OFTType::Hybrid => peer.pathway_type.clone().ok_or(OFTError::InvalidPathwayType.into()),
+            _ => Ok(self.oft_type.clone()),
+        }
+    }
+
+    pub fn can_mint(&self, supply_ld: u64, amount_ld: u64) -> bool {
+        match self.max_supply_ld {
+            Some(max_supply_ld) => {
+                supply_ld.checked_add(amount_ld).is_some_and(|supply| supply <= max_supply_ld)
+            },
+            None => true,
+        }
+    }
+
+    /// The amount that can still be minted under `max_supply_ld`, if any.
+    pub fn mint_capacity_ld(&self, supply_ld: u64) -> Option<u64> {
+        self.max_supply_ld.map(|max_supply_ld| max_supply_ld.saturating_sub(supply_ld))
+    }
+
     pub fn ld2sd(&self, amount_ld: u64) -> u64 {
         amount_ld / self.ld2sd_rate
     }
 
-    pub fn sd2ld(&self, amount_sd: u64) -> u64 {
-        amount_sd * self.ld2sd_rate
+    pub fn sd2ld(&self, amount_sd: u64) -> Result<u64> {
+        amount_sd.checked_mul(self.ld2sd_rate).ok_or(OFTError::AmountOverflow.into())
+    }
+
+    pub fn add_tvl(&mut self, amount_ld: u64) -> Result<()> {
+        self.tvl_ld = self.tvl_ld.checked_add(amount_ld).ok_or(OFTError::AmountOverflow)?;
+        Ok(())
+    }
+
+    pub fn sub_tvl(&mut self, amount_ld: u64) -> Result<()> {
+        self.tvl_ld = self.tvl_ld.checked_sub(amount_ld).ok_or(OFTError::TvlUnderflow)?;
+        Ok(())
+    }
+
+    /// The escrow balance owed to users, the admin and referrers, and the abandoned amounts.
+    pub fn booked_escrow_ld(&self) -> u128 {
+        self.tvl_ld as u128
+            + self.accrued_fee_ld as u128
+            + self.referral_fee_ld as u128
+            + self.abandoned_ld as u128
+    }
+
+    /// The referrer's share of `fee_ld`.
+    pub fn referral_share_of(&self, fee_ld: u64) -> u64 {
+        (fee_ld as u128 * self.referral_fee_share_bps as u128 / BPS_DENOMINATOR) as u64
+    }
+
+    pub fn add_referral_fee(&mut self, amount_ld: u64) -> Result<()> {
+        self.referral_fee_ld =
+            self.referral_fee_ld.checked_add(amount_ld).ok_or(OFTError::AmountOverflow)?;
+        Ok(())
+    }
+
+    pub fn add_abandoned(&mut self, amount_ld: u64) -> Result<()> {
+        self.abandoned_ld =
+            self.abandoned_ld.checked_add(amount_ld).ok_or(OFTError::AmountOverflow)?;
+        Ok(())
+    }
+
+    pub fn add_accrued_fee(&mut self, amount_ld: u64) -> Result<()> {
+        self.accrued_fee_ld =
+            self.accrued_fee_ld.checked_add(amount_ld).ok_or(OFTError::AmountOverflow)?;
+        Ok(())
     }
 
     pub fn remove_dust(&self, amount_ld: u64) -> u64 {
@@ -47,4 +132,5 @@ impl OFTStore {
 pub struct LzReceiveTypesAccounts {
     pub oft_store: Pubkey,
     pub token_mint: Pubkey,
+    pub transfer_hook_extra_metas: Pubkey, // default pubkey if the mint has no transfer hook
 }
@@ -2,6 +2,11 @@ use crate::*;
 
 pub const ENFORCED_OPTIONS_SEND_MAX_LEN: usize = 512;
 pub const ENFORCED_OPTIONS_SEND_AND_CALL_MAX_LEN: usize = 1024;
+pub const ENFORCED_OPTIONS_MAX_ENTRIES: usize = 4;
+pub const ENFORCED_OPTIONS_ENTRY_MAX_LEN: usize = 512;
+
+pub const MSG_TYPE_SEND: u16 = 1;
+pub const MSG_TYPE_SEND_AND_CALL: u16 = 2;
 
 #[account]
 #[derive(InitSpace)]
@@ -12,6 +17,144 @@ pub struct PeerConfig {
     pub inbound_rate_limiter: Option<RateLimiter>,
     pub fee_bps: Option<u16>,
     pub bump: u8,
+    pub fee_subsidy: Option<FeeSubsidy>,
+    pub pathway_type: Option<OFTType>, // only used by Hybrid stores, either Native or Adapter
+    // cumulative amounts in shared decimals, used to track the outstanding supply on the remote chain
+    pub total_sent_sd: u128,
+    pub total_received_sd: u128,
+    // if set, inbound amounts can not exceed total_sent_sd + credit_limit_sd
+    pub credit_limit_sd: Option<u64>,
+    // the message version sent to the peer, 0 is treated as msg_codec::MSG_VERSION_1
+    pub msg_version: u8,
+    // an old peer address still accepted on receive until the expiry, e.g. after a redeployment
+    pub secondary_sender: Option<SecondarySender>,
+    // if set, inbound messages must be received in nonce order after inbound_nonce. the endpoint
+    // tracks nonces per sender, so it can not be set together with a secondary_sender
+    pub ordered_inbound: bool,
+    pub inbound_nonce: u64, // the last nonce received, only tracked when ordered_inbound
+    // enforced options of msg types other than MSG_TYPE_SEND and MSG_TYPE_SEND_AND_CALL
+    #[max_len(ENFORCED_OPTIONS_MAX_ENTRIES)]
+    pub enforced_options_by_type: Vec<EnforcedOptionsEntry>,
+    // if set, the combined options of a send must satisfy the policy
+    pub executor_options_policy: Option<ExecutorOptionsPolicy>,
+}
+
+impl PeerConfig {
+    /// The net amount sent to the remote chain that has not come back.
+    pub fn outstanding_sd(&self) -> i128 {
+        self.total_sent_sd as i128 - self.total_received_sd as i128
+    }
+
+    /// The amount that can still be received under the credit limit, if any.
+    pub fn remaining_credit_sd(&self) -> Option<u128> {
+        self.credit_limit_sd.map(|credit_limit_sd| {
+            (self.total_sent_sd + credit_limit_sd as u128).saturating_sub(self.total_received_sd)
+        })
+    }
+
+    /// The enforced options of `msg_type`: the peer's if set, otherwise the store default.
+    pub fn get_enforced_options(&self, oft_store: &OFTStore, msg_type: u16) -> Vec<u8> {
+        let peer_options = match msg_type {
+            MSG_TYPE_SEND => {
+                Some(&self.enforced_options.send).filter(|options| !options.is_empty())
+            },
+            MSG_TYPE_SEND_AND_CALL => {
+                Some(&self.enforced_options.send_and_call).filter(|options| !options.is_empty())
+            },
+            _ => EnforcedOptionsEntry::find(&self.enforced_options_by_type, msg_type),
+        };
+        peer_options
+            .or_else(|| EnforcedOptionsEntry::find(&oft_store.default_enforced_options, msg_type))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn combine_options(
+        &self,
+        oft_store: &OFTStore,
+        msg_type: u16,
+        extra_options: &Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        let enforced_options = self.get_enforced_options(oft_store, msg_type);
+        let options = oapp::options::combine_options(enforced_options, extra_options)?;
+        if let Some(policy) = &self.executor_options_policy {
+            let executor_options =
+                options::decode_executor_options(&options).map_err(|_| OFTError::InvalidOptions)?;
+            policy.check(&executor_options)?;
+        }
+        Ok(options)
+    }
+
+    /// Whether inbound messages from `sender` are accepted. Sends always target `peer_address`.
+    pub fn accepts_sender(&self, sender: &[u8; 32]) -> Result<bool> {
+        if self.peer_address == *sender {
+            return Ok(true);
+        }
+        match &self.secondary_sender {
+            Some(secondary) if secondary.address == *sender => {
+                Ok(current_timestamp()? < secondary.expiry)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    /// Tracks the received nonce, enforcing the order if `ordered_inbound` is set.
+    pub fn record_nonce(&mut self, nonce: u64) -> Result<()> {
+        if self.ordered_inbound {
+            require!(Some(nonce) == self.inbound_nonce.checked_add(1), OFTError::InvalidNonce);
+            self.inbound_nonce = nonce;
+        }
+        Ok(())
+    }
+
+    pub fn record_sent(&mut self, amount_sd: u64) {
+        self.total_sent_sd = self.total_sent_sd.saturating_add(amount_sd as u128);
+    }
+
+    /// Records an inbound amount that will never be delivered, ignoring the credit limit.
+    pub fn record_abandoned(&mut self, amount_sd: u64) {
+        self.total_received_sd = self.total_received_sd.saturating_add(amount_sd as u128);
+    }
+
+    pub fn record_received(&mut self, amount_sd: u64) -> Result<()> {
+        if let Some(remaining_credit_sd) = self.remaining_credit_sd() {
+            require!(amount_sd as u128 <= remaining_credit_sd, OFTError::CreditLimitExceeded);
+        }
+        self.total_received_sd = self.total_received_sd.saturating_add(amount_sd as u128);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct SecondarySender {
+    pub address: [u8; 32],
+    pub expiry: u64, // unix timestamp, exclusive
+}
+
+/// Bounds on the executor options of a send, so that the message can be executed on the remote.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ExecutorOptionsPolicy {
+    pub min_lz_receive_gas: u128,
+    pub max_native_drop: u128, // the total of all native drops
+    pub max_compose_gas: u128, // per compose index
+}
+
+impl ExecutorOptionsPolicy {
+    pub fn check(&self, executor_options: &options::ExecutorOptions) -> Result<()> {
+        require!(
+            executor_options.lz_receive_gas() >= self.min_lz_receive_gas,
+            OFTError::LzReceiveGasTooLow
+        );
+        require!(
+            executor_options.total_native_drop() <= self.max_native_drop,
+            OFTError::NativeDropTooHigh
+        );
+        require!(
+            executor_options.max_lz_compose_gas() <= self.max_compose_gas,
+            OFTError::ComposeGasTooHigh
+        );
+        Ok(())
+    }
 }
 
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
@@ -32,22 +175,39 @@ impl RateLimiter {
     pub fn set_capacity(&mut self, capacity: u64) -> Result<()> {
         self.capacity = capacity;
         self.tokens = capacity;
-        self.last_refill_time = Clock::get()?.unix_timestamp.try_into().unwrap();
+        self.last_refill_time = current_timestamp()?;
         Ok(())
     }
 
     pub fn refill(&mut self, extra_tokens: u64) -> Result<()> {
-        let mut new_tokens = extra_tokens;
-        let current_time: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        let current_time = current_timestamp()?;
+        self.tokens =
+            std::cmp::min(self.capacity, self.tokens_at(current_time).saturating_add(extra_tokens));
+
+        self.last_refill_time = current_time;
+        Ok(())
+    }
+
+    /// The tokens available at `current_time`, without consuming or refilling.
+    pub fn tokens_at(&self, current_time: u64) -> u64 {
+        let mut new_tokens = 0;
         if current_time > self.last_refill_time {
             let time_elapsed_in_seconds = current_time - self.last_refill_time;
-            new_tokens = new_tokens
-                .saturating_add(time_elapsed_in_seconds.saturating_mul(self.refill_per_second));
+            new_tokens = time_elapsed_in_seconds.saturating_mul(self.refill_per_second);
         }
-        self.tokens = std::cmp::min(self.capacity, self.tokens.saturating_add(new_tokens));
+        std::cmp::min(self.capacity, self.tokens.saturating_add(new_tokens))
+    }
 
-        self.last_refill_time = current_time;
-        Ok(())
+    /// The seconds from `current_time` until the limiter is full, or None if it never refills.
+    pub fn seconds_to_full(&self, current_time: u64) -> Option<u64> {
+        let missing = self.capacity - self.tokens_at(current_time);
+        if missing == 0 {
+            Some(0)
+        } else if self.refill_per_second == 0 {
+            None
+        } else {
+            Some(missing.div_ceil(self.refill_per_second))
+        }
     }
 
     pub fn try_consume(&mut self, amount: u64) -> Result<()> {
@@ -62,6 +222,41 @@ impl RateLimiter {
     }
 }
 
+pub fn current_timestamp() -> Result<u64> {
+    Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .map_err(|_| OFTError::InvalidClock.into())
+}
+
+/// FeeSubsidy configures how much of the messaging native fee is paid from the SubsidyVault.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeSubsidy {
+    pub subsidy_bps: u16,
+    pub max_per_transfer: u64,
+}
+
+impl FeeSubsidy {
+    /// The subsidy applied to a full messaging fee, capped by `limit`.
+    pub fn subsidy_for_fee(&self, native_fee: u64, limit: u64) -> u64 {
+        let uncapped = (native_fee as u128 * self.subsidy_bps as u128 / BPS_DENOMINATOR) as u64;
+        uncapped.min(self.max_per_transfer).min(limit)
+    }
+
+    /// The largest subsidy needed when the user pays at most `user_fee`, i.e. the inverse of
+    /// `subsidy_for_fee`. It is capped by `limit`.
+    pub fn subsidy_for_user_fee(&self, user_fee: u64, limit: u64) -> u64 {
+        let uncapped = if self.subsidy_bps >= MAX_FEE_BASIS_POINTS {
+            u64::MAX
+        } else {
+            let user_bps = BPS_DENOMINATOR - self.subsidy_bps as u128;
+            u64::try_from(user_fee as u128 * self.subsidy_bps as u128 / user_bps)
+                .unwrap_or(u64::MAX)
+        };
+        uncapped.min(self.max_per_transfer).min(limit)
+    }
+}
+
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct EnforcedOptions {
     #[max_len(ENFORCED_OPTIONS_SEND_MAX_LEN)]
@@ -71,21 +266,45 @@ pub struct EnforcedOptions {
 }
 
 impl EnforcedOptions {
-    pub fn get_enforced_options(&self, composed_msg: &Option<Vec<u8>>) -> Vec<u8> {
-        if composed_msg.is_none() {
-            self.send.clone()
+    /// The msg type of an OFT send.
+    pub fn msg_type(compose_msg: &Option<Vec<u8>>) -> u16 {
+        if compose_msg.is_none() {
+            MSG_TYPE_SEND
         } else {
-            self.send_and_call.clone()
+            MSG_TYPE_SEND_AND_CALL
         }
     }
+}
 
-    pub fn combine_options(
-        &self,
-        compose_msg: &Option<Vec<u8>>,
-        extra_options: &Vec<u8>,
-    ) -> Result<Vec<u8>> {
-        let enforced_options = self.get_enforced_options(compose_msg);
-        oapp::options::combine_options(enforced_options, extra_options)
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct EnforcedOptionsEntry {
+    pub msg_type: u16,
+    #[max_len(ENFORCED_OPTIONS_ENTRY_MAX_LEN)]
+    pub options: Vec<u8>,
+}
+
+impl EnforcedOptionsEntry {
+    pub fn find(entries: &[EnforcedOptionsEntry], msg_type: u16) -> Option<&Vec<u8>> {
+        entries
+            .iter()
+            .find(|entry| entry.msg_type == msg_type)
+            .map(|entry| &entry.options)
+    }
+
+    /// Sets the options of `msg_type`, removing the entry if `options` is empty.
+    pub fn set(
+        entries: &mut Vec<EnforcedOptionsEntry>,
+        msg_type: u16,
+        options: Vec<u8>,
+    ) -> Result<()> {
+        oapp::options::assert_type_3(&options)?;
+        require!(options.len() <= ENFORCED_OPTIONS_ENTRY_MAX_LEN, OFTError::InvalidOptions);
+        entries.retain(|entry| entry.msg_type != msg_type);
+        if !options.is_empty() {
+            require!(entries.len() < ENFORCED_OPTIONS_MAX_ENTRIES, OFTError::InvalidOptions);
+            entries.push(EnforcedOptionsEntry { msg_type, options });
+        }
+        Ok(())
     }
 }
 
@@ -0,0 +1,11 @@
+use crate::*;
+
+/// Referrer accrues the referral share of the OFT fees of the sends it referred. The fees are held
+/// in the escrow until the referrer claims them.
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub referrer: Pubkey,
+    pub accrued_fee_ld: u64,
+    pub bump: u8,
+}
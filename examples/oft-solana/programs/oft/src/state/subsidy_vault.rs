@@ -0,0 +1,36 @@
+use crate::*;
+
+/// SubsidyVault holds the SOL used to sponsor the LayerZero messaging fee of outbound transfers.
+/// Anyone can fund it with a plain system transfer to the PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct SubsidyVault {
+    pub max_per_epoch: u64,
+    pub epoch: u64,
+    pub spent_in_epoch: u64,
+    pub bump: u8,
+}
+
+impl SubsidyVault {
+    pub fn remaining_in_epoch(&self, epoch: u64) -> u64 {
+        if epoch == self.epoch {
+            self.max_per_epoch.saturating_sub(self.spent_in_epoch)
+        } else {
+            self.max_per_epoch
+        }
+    }
+
+    pub fn record_spent(&mut self, epoch: u64, amount: u64) {
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.spent_in_epoch = 0;
+        }
+        self.spent_in_epoch = self.spent_in_epoch.saturating_add(amount);
+    }
+
+    /// lamports above the rent-exempt minimum that can be spent or withdrawn
+    pub fn spare_lamports(info: &AccountInfo) -> Result<u64> {
+        let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info.lamports().saturating_sub(rent_exempt))
+    }
+}
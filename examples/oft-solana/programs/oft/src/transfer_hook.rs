@@ -0,0 +1,136 @@
+use crate::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook, StateWithExtensions},
+    onchain,
+    state::Mint as MintState,
+};
+use oapp::endpoint_cpi::LzAccount;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+use spl_type_length_value::state::TlvStateBorrowed;
+
+// Token-2022 mints with the TransferHook extension need extra accounts on every `transfer_checked`.
+// They are passed at the front of the remaining accounts in this order:
+//      0 - transfer hook program
+//      1 - extra account metas (validation) account
+//      2.. - extra accounts resolved from the validation account
+
+pub fn get_hook_program_id(token_mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    let token_mint_data = token_mint.try_borrow_data()?;
+    let token_mint_ext = StateWithExtensions::<MintState>::unpack(&token_mint_data)?;
+    Ok(transfer_hook::get_program_id(&token_mint_ext))
+}
+
+/// Returns the extra account metas address of the mint's transfer hook, or the default pubkey if
+/// the mint has no transfer hook.
+pub fn get_hook_extra_metas_address(token_mint: &AccountInfo) -> Result<Pubkey> {
+    Ok(get_hook_program_id(token_mint)?
+        .map(|program_id| get_extra_account_metas_address(token_mint.key, &program_id))
+        .unwrap_or_default())
+}
+
+/// Splits `accounts` into the leading transfer hook accounts and the rest.
+pub fn split_hook_accounts<'a, 'info>(
+    token_mint: &AccountInfo<'info>,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let Some(program_id) = get_hook_program_id(token_mint)? else {
+        return Ok((&[], accounts));
+    };
+    require!(
+        accounts.len() >= 2
+            && accounts[0].key() == program_id
+            && accounts[1].key() == get_extra_account_metas_address(token_mint.key, &program_id),
+        OFTError::InvalidTransferHookAccounts
+    );
+    let extra_metas_len = {
+        let data = accounts[1].try_borrow_data()?;
+        if data.is_empty() {
+            0
+        } else {
+            let state = TlvStateBorrowed::unpack(&data)?;
+            ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)?
+                .data()
+                .len()
+        }
+    };
+    let hook_accounts_len = 2 + extra_metas_len;
+    require!(accounts.len() >= hook_accounts_len, OFTError::InvalidTransferHookAccounts);
+    Ok(accounts.split_at(hook_accounts_len))
+}
+
+/// `transfer_checked` that forwards the extra accounts required by the mint's transfer hook.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    token_mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        token_mint.clone(),
+        to.clone(),
+        authority.clone(),
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Resolves the transfer hook accounts for a transfer, in the order expected by
+/// `split_hook_accounts`. Extra accounts whose seeds depend on account data are not supported.
+pub fn get_hook_lz_accounts(
+    token_mint: &AccountInfo,
+    extra_metas: Option<&AccountInfo>,
+    source: Pubkey,
+    destination: Pubkey,
+    authority: Pubkey,
+    amount: u64,
+) -> Result<Vec<LzAccount>> {
+    let Some(program_id) = get_hook_program_id(token_mint)? else {
+        return Ok(vec![]);
+    };
+    let extra_metas_address = get_extra_account_metas_address(token_mint.key, &program_id);
+    let mut accounts = vec![
+        LzAccount { pubkey: program_id, is_signer: false, is_writable: false },
+        LzAccount { pubkey: extra_metas_address, is_signer: false, is_writable: false },
+    ];
+
+    let extra_metas = extra_metas
+        .filter(|info| info.key() == extra_metas_address)
+        .ok_or(OFTError::InvalidTransferHookAccounts)?;
+    let data = extra_metas.try_borrow_data()?;
+    if data.is_empty() {
+        return Ok(accounts);
+    }
+    let state = TlvStateBorrowed::unpack(&data)?;
+    let metas = ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)?;
+
+    // the execute instruction accounts are [source, mint, destination, authority, validation]
+    let instruction_data = TransferHookInstruction::Execute { amount }.pack();
+    let mut keys = vec![source, token_mint.key(), destination, authority, extra_metas_address];
+    for meta in metas.data() {
+        let resolved = meta.resolve(&instruction_data, &program_id, |index| {
+            keys.get(index).map(|key| (key, None))
+        })?;
+        keys.push(resolved.pubkey);
+        accounts.push(LzAccount {
+            pubkey: resolved.pubkey,
+            is_signer: false,
+            is_writable: resolved.is_writable,
+        });
+    }
+    Ok(accounts)
+}
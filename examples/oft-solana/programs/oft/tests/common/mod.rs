@@ -0,0 +1,26 @@
+use anchor_lang::prelude::Pubkey;
+use oft::state::{OFTStore, OFTType};
+
+pub fn oft_store(ld2sd_rate: u64) -> OFTStore {
+    OFTStore {
+        oft_type: OFTType::Adapter,
+        ld2sd_rate,
+        token_mint: Pubkey::default(),
+        token_escrow: Pubkey::default(),
+        endpoint_program: Pubkey::default(),
+        bump: 0,
+        tvl_ld: 0,
+        admin: Pubkey::default(),
+        default_fee_bps: 0,
+        paused: false,
+        pauser: None,
+        unpauser: None,
+        mint_flags: 0,
+        max_supply_ld: None,
+        accrued_fee_ld: 0,
+        referral_fee_share_bps: 0,
+        referral_fee_ld: 0,
+        default_enforced_options: vec![],
+        abandoned_ld: 0,
+    }
+}
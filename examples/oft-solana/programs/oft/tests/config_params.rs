@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod test_config_params {
+    use anchor_lang::AnchorSerialize;
+    use oft::instructions::{PeerConfigParam, SetOFTConfigParams};
+
+    // the variant index is the first byte of the params, and must match the one of the SDK
+    fn variant_index<T: AnchorSerialize>(param: &T) -> u8 {
+        param.try_to_vec().unwrap()[0]
+    }
+
+    #[test]
+    fn test_peer_config_param_indexes() {
+        assert_eq!(variant_index(&PeerConfigParam::PeerAddress([0; 32])), 0);
+        assert_eq!(variant_index(&PeerConfigParam::FeeBps(None)), 1);
+        assert_eq!(
+            variant_index(&PeerConfigParam::EnforcedOptions {
+                send: vec![],
+                send_and_call: vec![]
+            }),
+            2
+        );
+        assert_eq!(variant_index(&PeerConfigParam::OutboundRateLimit(None)), 3);
+        assert_eq!(variant_index(&PeerConfigParam::InboundRateLimit(None)), 4);
+    }
+
+    #[test]
+    fn test_oft_config_param_indexes() {
+        assert_eq!(variant_index(&SetOFTConfigParams::Admin(Default::default())), 0);
+        assert_eq!(variant_index(&SetOFTConfigParams::Delegate(Default::default())), 1);
+        assert_eq!(variant_index(&SetOFTConfigParams::DefaultFee(0)), 2);
+        assert_eq!(variant_index(&SetOFTConfigParams::Paused(true)), 3);
+        assert_eq!(variant_index(&SetOFTConfigParams::Pauser(None)), 4);
+        assert_eq!(variant_index(&SetOFTConfigParams::Unpauser(None)), 5);
+    }
+}
@@ -0,0 +1,102 @@
+mod common;
+
+#[cfg(test)]
+mod test_executor_options_policy {
+    use super::common::oft_store;
+    use ::oft::{errors::OFTError, options::OptionsBuilder, state::*};
+
+    const RECEIVER: [u8; 32] = [0x11; 32];
+
+    fn peer(policy: ExecutorOptionsPolicy) -> PeerConfig {
+        PeerConfig {
+            peer_address: [1; 32],
+            enforced_options: EnforcedOptions::default(),
+            outbound_rate_limiter: None,
+            inbound_rate_limiter: None,
+            fee_bps: None,
+            bump: 0,
+            fee_subsidy: None,
+            pathway_type: None,
+            total_sent_sd: 0,
+            total_received_sd: 0,
+            credit_limit_sd: None,
+            msg_version: 0,
+            secondary_sender: None,
+            ordered_inbound: false,
+            inbound_nonce: 0,
+            enforced_options_by_type: vec![],
+            executor_options_policy: Some(policy),
+        }
+    }
+
+    fn policy() -> ExecutorOptionsPolicy {
+        ExecutorOptionsPolicy {
+            min_lz_receive_gas: 200_000,
+            max_native_drop: 1_000,
+            max_compose_gas: 100_000,
+        }
+    }
+
+    fn combine(options: &Vec<u8>) -> anchor_lang::Result<Vec<u8>> {
+        peer(policy()).combine_options(&oft_store(1), MSG_TYPE_SEND, options)
+    }
+
+    #[test]
+    fn test_accepts_options_within_policy() {
+        let options = OptionsBuilder::new()
+            .add_executor_lz_receive_option(150_000, 0)
+            .add_executor_lz_receive_option(50_000, 0)
+            .add_executor_native_drop_option(600, RECEIVER)
+            .add_executor_native_drop_option(400, [0x22; 32])
+            .add_executor_lz_compose_option(0, 100_000, 0)
+            .add_executor_lz_compose_option(1, 100_000, 0)
+            .build();
+        assert_eq!(combine(&options).unwrap(), options);
+    }
+
+    #[test]
+    fn test_lz_receive_gas_too_low() {
+        let options = OptionsBuilder::new().add_executor_lz_receive_option(199_999, 0).build();
+        assert_eq!(combine(&options).unwrap_err(), OFTError::LzReceiveGasTooLow.into());
+        // no lzReceive option at all is no gas
+        assert_eq!(combine(&vec![]).unwrap_err(), OFTError::LzReceiveGasTooLow.into());
+    }
+
+    #[test]
+    fn test_native_drop_too_high() {
+        // the drops are bounded in total, not per receiver
+        let options = OptionsBuilder::new()
+            .add_executor_lz_receive_option(200_000, 0)
+            .add_executor_native_drop_option(600, RECEIVER)
+            .add_executor_native_drop_option(401, [0x22; 32])
+            .build();
+        assert_eq!(combine(&options).unwrap_err(), OFTError::NativeDropTooHigh.into());
+    }
+
+    #[test]
+    fn test_compose_gas_too_high() {
+        // the gas is bounded per compose index, after merging the options of the same index
+        let options = OptionsBuilder::new()
+            .add_executor_lz_receive_option(200_000, 0)
+            .add_executor_lz_compose_option(0, 60_000, 0)
+            .add_executor_lz_compose_option(1, 60_000, 0)
+            .add_executor_lz_compose_option(0, 40_001, 0)
+            .build();
+        assert_eq!(combine(&options).unwrap_err(), OFTError::ComposeGasTooHigh.into());
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        // truncated lzReceive gas
+        let options = vec![0, 3, 1, 0, 3, 1, 0, 0];
+        assert_eq!(combine(&options).unwrap_err(), OFTError::InvalidOptions.into());
+    }
+
+    #[test]
+    fn test_no_policy() {
+        let mut peer = peer(policy());
+        peer.executor_options_policy = None;
+        let options = vec![0, 3, 1, 0, 3, 1, 0, 0];
+        assert_eq!(peer.combine_options(&oft_store(1), MSG_TYPE_SEND, &options).unwrap(), options);
+    }
+}
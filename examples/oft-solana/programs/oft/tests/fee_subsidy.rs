@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod test_fee_subsidy {
+    use oft::state::FeeSubsidy;
+
+    fn fee_subsidy(subsidy_bps: u16, max_per_transfer: u64) -> FeeSubsidy {
+        FeeSubsidy { subsidy_bps, max_per_transfer }
+    }
+
+    #[test]
+    fn test_subsidy_for_fee() {
+        let subsidy = fee_subsidy(2_500, u64::MAX);
+        assert_eq!(subsidy.subsidy_for_fee(1_000, u64::MAX), 250);
+        // rounds down
+        assert_eq!(subsidy.subsidy_for_fee(1_003, u64::MAX), 250);
+        assert_eq!(subsidy.subsidy_for_fee(u64::MAX, u64::MAX), u64::MAX / 4);
+        assert_eq!(fee_subsidy(0, u64::MAX).subsidy_for_fee(1_000, u64::MAX), 0);
+        assert_eq!(fee_subsidy(10_000, u64::MAX).subsidy_for_fee(1_000, u64::MAX), 1_000);
+    }
+
+    #[test]
+    fn test_subsidy_for_user_fee() {
+        let subsidy = fee_subsidy(2_500, u64::MAX);
+        // the user pays 750 of a 1000 fee
+        assert_eq!(subsidy.subsidy_for_user_fee(750, u64::MAX), 250);
+        assert_eq!(fee_subsidy(0, u64::MAX).subsidy_for_user_fee(1_000, u64::MAX), 0);
+        // a full subsidy has no bound but the caps
+        assert_eq!(fee_subsidy(10_000, 500).subsidy_for_user_fee(0, u64::MAX), 500);
+        assert_eq!(fee_subsidy(9_999, u64::MAX).subsidy_for_user_fee(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_subsidy_inverse() {
+        // pre-funding the subsidy for the user fee covers the subsidy of the resulting full fee,
+        // and the user never pays more than the quoted user fee
+        for subsidy_bps in [1, 2_500, 3_333, 5_000, 9_999] {
+            let subsidy = fee_subsidy(subsidy_bps, u64::MAX);
+            for user_fee in (0..10_000).chain([1_000_003, 123_456_789]) {
+                let pre_funded = subsidy.subsidy_for_user_fee(user_fee, u64::MAX);
+                let native_fee = user_fee + pre_funded;
+                let subsidized = subsidy.subsidy_for_fee(native_fee, pre_funded);
+                assert!(subsidized <= pre_funded);
+                assert!(native_fee - subsidized <= user_fee);
+            }
+        }
+    }
+
+    #[test]
+    fn test_subsidy_caps() {
+        let subsidy = fee_subsidy(5_000, 100);
+        assert_eq!(subsidy.subsidy_for_fee(1_000, u64::MAX), 100);
+        assert_eq!(subsidy.subsidy_for_fee(1_000, 40), 40);
+        assert_eq!(subsidy.subsidy_for_user_fee(1_000, u64::MAX), 100);
+        assert_eq!(subsidy.subsidy_for_user_fee(1_000, 40), 40);
+        assert_eq!(subsidy.subsidy_for_user_fee(10, u64::MAX), 10);
+    }
+}
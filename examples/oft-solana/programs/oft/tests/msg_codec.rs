@@ -2,6 +2,7 @@
 mod test_msg_codec {
     use anchor_lang::prelude::Pubkey;
     use oft::compose_msg_codec;
+    use oft::errors::OFTError;
     use oft::msg_codec;
 
     #[test]
@@ -12,10 +13,10 @@ mod test_msg_codec {
         let compose_msg: Option<Vec<u8>> = Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
         let encoded = msg_codec::encode(send_to, amount_sd, sender, &compose_msg);
         assert_eq!(encoded.len(), 72 + compose_msg.clone().unwrap().len());
-        assert_eq!(msg_codec::send_to(&encoded), send_to);
-        assert_eq!(msg_codec::amount_sd(&encoded), amount_sd);
+        assert_eq!(msg_codec::send_to(&encoded).unwrap(), send_to);
+        assert_eq!(msg_codec::amount_sd(&encoded).unwrap(), amount_sd);
         assert_eq!(
-            msg_codec::compose_msg(&encoded),
+            msg_codec::compose_msg(&encoded).unwrap(),
             Some([sender.to_bytes().as_ref(), compose_msg.unwrap().as_slice()].concat())
         );
     }
@@ -28,9 +29,9 @@ mod test_msg_codec {
         let compose_msg: Option<Vec<u8>> = None;
         let encoded = msg_codec::encode(send_to, amount_sd, sender, &compose_msg);
         assert_eq!(encoded.len(), 40);
-        assert_eq!(msg_codec::send_to(&encoded), send_to);
-        assert_eq!(msg_codec::amount_sd(&encoded), amount_sd);
-        assert_eq!(msg_codec::compose_msg(&encoded), None);
+        assert_eq!(msg_codec::send_to(&encoded).unwrap(), send_to);
+        assert_eq!(msg_codec::amount_sd(&encoded).unwrap(), amount_sd);
+        assert_eq!(msg_codec::compose_msg(&encoded).unwrap(), None);
     }
 
     #[test]
@@ -47,9 +48,135 @@ mod test_msg_codec {
             &[&compose_from[..], &compose_msg].concat(),
         );
         assert_eq!(encoded.len(), 20 + [&compose_from[..], &compose_msg].concat().len());
-        assert_eq!(compose_msg_codec::nonce(&encoded), nonce);
-        assert_eq!(compose_msg_codec::src_eid(&encoded), src_eid);
-        assert_eq!(compose_msg_codec::amount_ld(&encoded), amount_ld);
-        assert_eq!(compose_msg_codec::compose_msg(&encoded), compose_msg);
+        assert_eq!(compose_msg_codec::nonce(&encoded).unwrap(), nonce);
+        assert_eq!(compose_msg_codec::src_eid(&encoded).unwrap(), src_eid);
+        assert_eq!(compose_msg_codec::amount_ld(&encoded).unwrap(), amount_ld);
+        assert_eq!(compose_msg_codec::compose_msg(&encoded).unwrap(), compose_msg);
+    }
+
+    #[test]
+    fn test_msg_codec_invalid_length() {
+        let encoded = msg_codec::encode([1; 32], 1, Pubkey::new_unique(), &Some(vec![]));
+        assert!(msg_codec::validate(&encoded).is_ok());
+        for len in [0, 39, 41, 71] {
+            let message = &encoded[..len];
+            assert_eq!(msg_codec::validate(message).unwrap_err(), OFTError::InvalidMessage.into());
+            assert_eq!(msg_codec::send_to(message).is_err(), len < 40);
+            assert_eq!(msg_codec::amount_sd(message).is_err(), len < 40);
+            assert!(msg_codec::compose_msg(message).is_err());
+        }
+    }
+
+    #[test]
+    fn test_msg_codec_v2() {
+        let sender = Pubkey::new_unique();
+        let compose_msg = Some(vec![1, 2, 3]);
+        let extensions = msg_codec::MsgExtensions {
+            memo: Some(b"memo".to_vec()),
+            referral_id: Some([7; 32]),
+            origin_timestamp: Some(1_700_000_000),
+            compliance_tag: None,
+        };
+        let v1 = msg_codec::encode([1; 32], 42, sender, &compose_msg);
+        let v2 = msg_codec::encode_versioned(
+            msg_codec::MSG_VERSION_2,
+            [1; 32],
+            42,
+            sender,
+            &compose_msg,
+            &Some(extensions.clone()),
+        )
+        .unwrap();
+        assert_eq!(&v2[..v1.len()], v1.as_slice());
+        assert_eq!(msg_codec::amount_sd(&v2).unwrap(), 42);
+        let (body, decoded) = msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &v2).unwrap();
+        assert_eq!(body, v1.as_slice());
+        assert_eq!(decoded, extensions);
+        // the composer receives the same compose msg as from a v1 message
+        let mut expected_compose_msg = sender.to_bytes().to_vec();
+        expected_compose_msg.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(msg_codec::compose_msg(body).unwrap(), Some(expected_compose_msg.clone()));
+
+        // v1 messages have no extensions, even if the compose msg looks like a trailer
+        let (body, decoded) = msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &v1).unwrap();
+        assert_eq!(body, v1.as_slice());
+        assert_eq!(decoded, msg_codec::MsgExtensions::default());
+        let compose_msg = Some([&[0, 0, 0][..], &[0, 0], b"LZOFTEXT", &[1]].concat());
+        let v1 = msg_codec::encode([1; 32], 42, sender, &compose_msg);
+        assert_eq!(
+            msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &v1).unwrap().0,
+            v1.as_slice()
+        );
+
+        // the trailer is not parsed for peers on v1, even if it is a valid v2 trailer
+        let compose_msg = Some([&[0, 0, 0][..], &[0, 0], b"LZOFTEXT", &[2]].concat());
+        let v1 = msg_codec::encode([1; 32], 42, sender, &compose_msg);
+        for version in [0, msg_codec::MSG_VERSION_1] {
+            let (body, decoded) = msg_codec::split_extensions(version, &v1).unwrap();
+            assert_eq!(body, v1.as_slice());
+            assert_eq!(decoded, msg_codec::MsgExtensions::default());
+            let mut expected_compose_msg = sender.to_bytes().to_vec();
+            expected_compose_msg.extend_from_slice(compose_msg.as_ref().unwrap());
+            assert_eq!(msg_codec::compose_msg(body).unwrap(), Some(expected_compose_msg));
+        }
+        // a peer on v2 would strip it as an empty extensions trailer
+        let (body, _) = msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &v1).unwrap();
+        assert_eq!(body.len(), v1.len() - 11);
+
+        // v2 without extensions and without a compose msg
+        let v2 = msg_codec::encode_versioned(
+            msg_codec::MSG_VERSION_2,
+            [1; 32],
+            42,
+            sender,
+            &None,
+            &None,
+        )
+        .unwrap();
+        assert_eq!(v2.len(), 51);
+        let (body, decoded) = msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &v2).unwrap();
+        assert_eq!(msg_codec::compose_msg(body).unwrap(), None);
+        assert_eq!(decoded, msg_codec::MsgExtensions::default());
+
+        // v1 peers can not receive extensions
+        assert!(msg_codec::encode_versioned(
+            msg_codec::MSG_VERSION_1,
+            [1; 32],
+            42,
+            sender,
+            &None,
+            &Some(extensions)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_msg_extensions_decode() {
+        // unknown type 9 is skipped
+        let data = [&[9, 0, 2, 0xaa, 0xbb][..], &[1, 0, 1, b'm']].concat();
+        let extensions = msg_codec::MsgExtensions::decode(&data).unwrap();
+        assert_eq!(extensions.memo, Some(b"m".to_vec()));
+        // truncated value and wrong timestamp length are rejected
+        assert!(msg_codec::MsgExtensions::decode(&[1, 0, 2, b'm']).is_err());
+        assert!(msg_codec::MsgExtensions::decode(&[3, 0, 1, 0]).is_err());
+        // a message with an unknown trailer version is not v2, and is invalid as a v1 message
+        let message = [[0; 40].as_slice(), &[0, 0], &msg_codec::MSG_V2_MAGIC, &[3]].concat();
+        assert!(msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &message).is_err());
+        // the extensions length must fit in the message
+        let message = [[0; 40].as_slice(), &[0, 1], &msg_codec::MSG_V2_MAGIC, &[2]].concat();
+        assert!(msg_codec::split_extensions(msg_codec::MSG_VERSION_2, &message).is_err());
+    }
+
+    #[test]
+    fn test_compose_msg_codec_invalid_length() {
+        let encoded = compose_msg_codec::encode(1, 2, 3, &vec![0; 32]);
+        assert_eq!(compose_msg_codec::compose_msg(&encoded).unwrap(), Vec::<u8>::new());
+        let message = &encoded[..51];
+        assert_eq!(
+            compose_msg_codec::validate(message).unwrap_err(),
+            OFTError::InvalidMessage.into()
+        );
+        assert!(compose_msg_codec::nonce(message).is_err());
+        assert!(compose_msg_codec::compose_from(message).is_err());
     }
 }
@@ -0,0 +1,99 @@
+mod common;
+
+#[cfg(test)]
+mod test_oft_store {
+    use super::common::oft_store;
+    use ::oft::{errors::OFTError, state::*};
+
+    #[test]
+    fn test_rate_one() {
+        let store = oft_store(1);
+        assert_eq!(store.ld2sd(u64::MAX), u64::MAX);
+        assert_eq!(store.sd2ld(u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(store.remove_dust(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_max_rate() {
+        // the largest power of ten that fits in a u64
+        let rate = 10u64.pow(19);
+        let store = oft_store(rate);
+        assert_eq!(store.ld2sd(rate - 1), 0);
+        assert_eq!(store.ld2sd(u64::MAX), 1);
+        assert_eq!(store.remove_dust(u64::MAX), rate);
+        assert_eq!(store.sd2ld(1).unwrap(), rate);
+        assert_eq!(store.sd2ld(2).unwrap_err(), OFTError::AmountOverflow.into());
+    }
+
+    #[test]
+    fn test_sd2ld_overflow() {
+        let store = oft_store(1_000);
+        let max_sd = u64::MAX / 1_000;
+        assert_eq!(store.sd2ld(max_sd).unwrap(), max_sd * 1_000);
+        assert_eq!(store.sd2ld(max_sd + 1).unwrap_err(), OFTError::AmountOverflow.into());
+        assert_eq!(store.ld2sd(store.sd2ld(max_sd).unwrap()), max_sd);
+    }
+
+    #[test]
+    fn test_tvl() {
+        let mut store = oft_store(1);
+        store.add_tvl(u64::MAX).unwrap();
+        assert_eq!(store.add_tvl(1).unwrap_err(), OFTError::AmountOverflow.into());
+        store.sub_tvl(u64::MAX).unwrap();
+        assert_eq!(store.sub_tvl(1).unwrap_err(), OFTError::TvlUnderflow.into());
+        assert_eq!(store.tvl_ld, 0);
+    }
+
+    #[test]
+    fn test_booked_escrow() {
+        let mut store = oft_store(1);
+        store.add_tvl(100).unwrap();
+        store.add_accrued_fee(10).unwrap();
+        store.add_referral_fee(1).unwrap();
+        // abandoned amounts move out of the tvl but stay booked, so reconcile can not sweep them
+        store.sub_tvl(40).unwrap();
+        store.add_abandoned(40).unwrap();
+        assert_eq!(store.booked_escrow_ld(), 111);
+        store.abandoned_ld = u64::MAX;
+        assert_eq!(store.add_abandoned(1).unwrap_err(), OFTError::AmountOverflow.into());
+    }
+
+    #[test]
+    fn test_referral_share() {
+        let mut store = oft_store(1);
+        assert_eq!(store.referral_share_of(u64::MAX), 0);
+        store.referral_fee_share_bps = 2_500;
+        assert_eq!(store.referral_share_of(1_003), 250);
+        store.referral_fee_share_bps = 10_000;
+        assert_eq!(store.referral_share_of(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_default_enforced_options() {
+        let mut store = oft_store(1);
+        let options = vec![0, 3, 1, 0, 17, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x30, 0xd4];
+        EnforcedOptionsEntry::set(&mut store.default_enforced_options, 7, options.clone()).unwrap();
+        EnforcedOptionsEntry::set(&mut store.default_enforced_options, 8, options.clone()).unwrap();
+        assert_eq!(EnforcedOptionsEntry::find(&store.default_enforced_options, 7), Some(&options));
+        assert_eq!(EnforcedOptionsEntry::find(&store.default_enforced_options, 9), None);
+
+        // empty options remove the entry
+        EnforcedOptionsEntry::set(&mut store.default_enforced_options, 7, vec![]).unwrap();
+        assert_eq!(EnforcedOptionsEntry::find(&store.default_enforced_options, 7), None);
+        assert_eq!(store.default_enforced_options.len(), 1);
+
+        for msg_type in 9..=11 {
+            EnforcedOptionsEntry::set(
+                &mut store.default_enforced_options,
+                msg_type,
+                options.clone(),
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            EnforcedOptionsEntry::set(&mut store.default_enforced_options, 12, options)
+                .unwrap_err(),
+            OFTError::InvalidOptions.into()
+        );
+    }
+}
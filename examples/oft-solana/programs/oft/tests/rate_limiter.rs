@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod test_rate_limiter {
+    use ::oft::state::RateLimiter;
+
+    #[test]
+    fn test_tokens_at() {
+        let rate_limiter = RateLimiter {
+            capacity: 100,
+            tokens: 40,
+            refill_per_second: 7,
+            last_refill_time: 1_000,
+        };
+        assert_eq!(rate_limiter.tokens_at(999), 40);
+        assert_eq!(rate_limiter.tokens_at(1_000), 40);
+        assert_eq!(rate_limiter.tokens_at(1_005), 75);
+        assert_eq!(rate_limiter.tokens_at(1_009), 100);
+        assert_eq!(rate_limiter.tokens_at(u64::MAX), 100);
+    }
+
+    #[test]
+    fn test_seconds_to_full() {
+        let mut rate_limiter = RateLimiter {
+            capacity: 100,
+            tokens: 40,
+            refill_per_second: 7,
+            last_refill_time: 1_000,
+        };
+        assert_eq!(rate_limiter.seconds_to_full(1_000), Some(9));
+        assert_eq!(rate_limiter.seconds_to_full(1_005), Some(4));
+        assert_eq!(rate_limiter.seconds_to_full(1_009), Some(0));
+        rate_limiter.refill_per_second = 0;
+        assert_eq!(rate_limiter.seconds_to_full(2_000), None);
+        rate_limiter.tokens = 100;
+        assert_eq!(rate_limiter.seconds_to_full(2_000), Some(0));
+    }
+}
@@ -13,8 +13,13 @@ import {
     publicKey,
     transactionBuilder,
 } from '@metaplex-foundation/umi'
-import { fromWeb3JsPublicKey, toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
-import { TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { fromWeb3JsInstruction, fromWeb3JsPublicKey, toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
+import {
+    AuthorityType as SplAuthorityType,
+    TOKEN_PROGRAM_ID,
+    createSetAuthorityInstruction,
+    getMint,
+} from '@solana/spl-token'
 import { PublicKey } from '@solana/web3.js'
 import bs58 from 'bs58'
 import { task } from 'hardhat/config'
@@ -27,6 +32,7 @@ import { EndpointId } from '@layerzerolabs/lz-definitions'
 import { OFT_DECIMALS as DEFAULT_SHARED_DECIMALS, oft } from '@layerzerolabs/oft-v2-solana-sdk'
 
 import { checkMultisigSigners, createMintAuthorityMultisig } from './multisig'
+import { withInitOftMintAuthority } from './utils'
 
 import {
     TransactionType,
@@ -231,6 +237,29 @@ task('lz:oft:solana:create', 'Mints new SPL Token and creates new OFT Store acco
 
             // EOF: Validate combination of parameters
 
+            // BOF: check the Mint Authority of the MABA mint
+            // important: init_oft requires the Mint Authority to be the OFT Store or the Mint Authority Multisig, so
+            // the wallet must hold it to transfer it before init_oft, unless it already is the OFT Store
+            let currentMintAuthority: PublicKey | null = null
+            if (mintStr) {
+                const mintInfo = await getMint(
+                    connection,
+                    new PublicKey(mintStr),
+                    undefined,
+                    toWeb3JsPublicKey(tokenProgramId)
+                )
+                currentMintAuthority = mintInfo.mintAuthority
+                const isWalletMintAuthority = currentMintAuthority?.equals(toWeb3JsPublicKey(umiWalletSigner.publicKey))
+                const isOftStoreMintAuthority =
+                    onlyOftStore && currentMintAuthority?.equals(toWeb3JsPublicKey(oftStorePda))
+                if (!isWalletMintAuthority && !isOftStoreMintAuthority) {
+                    throw new Error(
+                        `The Mint Authority of ${mintStr} is ${currentMintAuthority?.toBase58() ?? 'renounced'}. To create a Mint-And-Burn Adapter, it must be held by the wallet ${umiWalletSigner.publicKey} so that it can be transferred to the OFT Store or the Mint Authority Multisig before init_oft.`
+                    )
+                }
+            }
+            // EOF: check the Mint Authority of the MABA mint
+
             const maxSupplyRaw = localDecimalsToMaxWholeTokens(decimals)
             const { full, compact } = formatTokenAmount(maxSupplyRaw)
             const maxSupplyStatement = `You have chosen ${decimals} local decimals. The maximum supply of your Solana OFT token will be ${full} (~${compact}).\n`
@@ -324,35 +353,7 @@ task('lz:oft:solana:create', 'Mints new SPL Token and creates new OFT Store acco
                 console.log(`createTokenTx: ${getExplorerTxLink(bs58.encode(createTokenTx.signature), isTestnet)}`)
             }
 
-            const lockboxSigner = createSignerFromKeypair({ eddsa: eddsa }, lockBox)
-            let txBuilder = transactionBuilder().add(
-                oft.initOft(
-                    {
-                        payer: umiWalletSigner,
-                        admin: umiWalletKeyPair.publicKey,
-                        mint: mint.publicKey,
-                        escrow: lockboxSigner,
-                    },
-                    oft.types.OFTType.Native,
-                    sharedDecimals,
-                    {
-                        oft: programId,
-                        token: tokenProgramId,
-                    }
-                )
-            )
-            txBuilder = await addComputeUnitInstructions(
-                connection,
-                umi,
-                eid,
-                txBuilder,
-                umiWalletSigner,
-                computeUnitPriceScaleFactor,
-                TransactionType.InitOft
-            )
-            const { signature } = await txBuilder.sendAndConfirm(umi)
-            console.log(`initOftTx: ${getExplorerTxLink(bs58.encode(signature), isTestnet)}`)
-
+            // init_oft requires the Mint Authority to be the OFT Store or the Mint Authority Multisig
             if (!isMABA) {
                 let txBuilder = transactionBuilder()
                     .add(
@@ -382,12 +383,65 @@ task('lz:oft:solana:create', 'Mints new SPL Token and creates new OFT Store acco
                 )
                 const { signature } = await txBuilder.sendAndConfirm(umi)
                 console.log(`setAuthorityTx: ${getExplorerTxLink(bs58.encode(signature), isTestnet)}`)
-            }
-            if (isMABA) {
-                console.log(
-                    `Please note that for MABA mode, you must carry out the change of Mint Authority before making any cross-chain transfers. For more details: https://github.com/LayerZero-Labs/devtools/tree/main/examples/oft-solana#for-oft-mint-and-burn-adapter-maba`
+            } else if (!currentMintAuthority?.equals(mintAuthorityPublicKey)) {
+                // the MABA mint can be a Token-2022 mint
+                const ix = createSetAuthorityInstruction(
+                    toWeb3JsPublicKey(mint.publicKey),
+                    toWeb3JsPublicKey(umiWalletSigner.publicKey),
+                    SplAuthorityType.MintTokens,
+                    mintAuthorityPublicKey,
+                    [],
+                    toWeb3JsPublicKey(tokenProgramId)
+                )
+                let txBuilder = transactionBuilder().add({
+                    instruction: fromWeb3JsInstruction(ix),
+                    signers: [umiWalletSigner],
+                    bytesCreatedOnChain: 0,
+                })
+                txBuilder = await addComputeUnitInstructions(
+                    connection,
+                    umi,
+                    eid,
+                    txBuilder,
+                    umiWalletSigner,
+                    computeUnitPriceScaleFactor,
+                    TransactionType.SetAuthority
                 )
+                const { signature } = await txBuilder.sendAndConfirm(umi)
+                console.log(`setAuthorityTx: ${getExplorerTxLink(bs58.encode(signature), isTestnet)}`)
             }
+
+            const lockboxSigner = createSignerFromKeypair({ eddsa: eddsa }, lockBox)
+            let txBuilder = withInitOftMintAuthority(
+                oft.initOft(
+                    {
+                        payer: umiWalletSigner,
+                        admin: umiWalletKeyPair.publicKey,
+                        mint: mint.publicKey,
+                        escrow: lockboxSigner,
+                    },
+                    oft.types.OFTType.Native,
+                    sharedDecimals,
+                    {
+                        oft: programId,
+                        token: tokenProgramId,
+                    }
+                ),
+                programId,
+                fromWeb3JsPublicKey(mintAuthorityPublicKey)
+            )
+            txBuilder = await addComputeUnitInstructions(
+                connection,
+                umi,
+                eid,
+                txBuilder,
+                umiWalletSigner,
+                computeUnitPriceScaleFactor,
+                TransactionType.InitOft
+            )
+            const { signature } = await txBuilder.sendAndConfirm(umi)
+            console.log(`initOftTx: ${getExplorerTxLink(bs58.encode(signature), isTestnet)}`)
+
             saveSolanaDeployment(
                 eid,
                 programIdStr,
@@ -1,4 +1,4 @@
-import { createSignerFromKeypair, publicKey, transactionBuilder } from '@metaplex-foundation/umi'
+import { createSignerFromKeypair, publicKey } from '@metaplex-foundation/umi'
 import { TOKEN_PROGRAM_ID, getMint } from '@solana/spl-token'
 import { PublicKey } from '@solana/web3.js'
 import bs58 from 'bs58'
@@ -11,6 +11,8 @@ import { promptToContinue } from '@layerzerolabs/io-devtools'
 import { EndpointId } from '@layerzerolabs/lz-definitions'
 import { OFT_DECIMALS, oft } from '@layerzerolabs/oft-v2-solana-sdk'
 
+import { withInitOftMintAuthority } from './utils'
+
 import {
     TransactionType,
     addComputeUnitInstructions,
@@ -78,7 +80,7 @@ task('lz:oft-adapter:solana:create', 'Creates new OFT Adapter (OFT Store PDA)')
 
             const mintAuthority = mintPDA.mintAuthority
 
-            let txBuilder = transactionBuilder().add(
+            let txBuilder = withInitOftMintAuthority(
                 oft.initOft(
                     {
                         payer: createSignerFromKeypair({ eddsa: eddsa }, umiWalletKeyPair),
@@ -92,7 +94,8 @@ task('lz:oft-adapter:solana:create', 'Creates new OFT Adapter (OFT Store PDA)')
                         oft: programId,
                         token: tokenProgram ? publicKey(tokenProgram) : undefined,
                     }
-                )
+                ),
+                programId
             )
             txBuilder = await addComputeUnitInstructions(
                 connection,
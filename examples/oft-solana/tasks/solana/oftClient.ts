@@ -0,0 +1,259 @@
+import { createHash } from 'node:crypto'
+
+import { AccountMeta, PublicKey, RpcInterface, Signer, WrappedInstruction, publicKey } from '@metaplex-foundation/umi'
+import { bool, bytes, option, struct, u32, u64 } from '@metaplex-foundation/umi/serializers'
+import { fromWeb3JsPublicKey, toWeb3JsInstruction, toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
+import { ComputeBudgetProgram, PublicKey as Web3PublicKey } from '@solana/web3.js'
+
+import {
+    EndpointProgram,
+    SimpleMessageLibProgram,
+    SolanaPacketPath,
+    UlnProgram,
+    simulateWeb3JsTransaction,
+} from '@layerzerolabs/lz-solana-sdk-v2/umi'
+import { OftPDA, oft } from '@layerzerolabs/oft-v2-solana-sdk'
+
+// Builders of the `quote_send` and `send` instructions of OFT programs of interface 3, which
+// @layerzerolabs/oft-v2-solana-sdk does not build yet. Mints with a transfer hook are not supported.
+
+const SYSTEM_PROGRAM_ID = publicKey('11111111111111111111111111111111')
+
+// The size of an `OFTStore` of interface 2: 8 + 1 + 8 + 32 + 32 + 32 + 1 + 8 + 32 + 2 + 1 + 33 + 33
+export const OFT_STORE_V2_SIZE = 223
+
+const versionSerializer = struct<{ interface: bigint; message: bigint }>([
+    ['interface', u64()],
+    ['message', u64()],
+])
+
+const messagingFeeSerializer = struct<{ nativeFee: bigint; lzTokenFee: bigint }>([
+    ['nativeFee', u64()],
+    ['lzTokenFee', u64()],
+])
+
+interface SendParamArgs {
+    dstEid: number
+    to: Uint8Array
+    amountLd: bigint
+    minAmountLd: bigint
+    options?: Uint8Array
+    composeMsg?: Uint8Array
+}
+
+const sendParamFields = [
+    ['dstEid', u32()],
+    ['to', bytes({ size: 32 })],
+    ['amountLd', u64()],
+    ['minAmountLd', u64()],
+    ['options', bytes({ size: u32() })],
+    ['composeMsg', option(bytes({ size: u32() }))],
+] as const
+
+const quoteSendParamsSerializer = struct<{
+    dstEid: number
+    to: Uint8Array
+    amountLd: bigint
+    minAmountLd: bigint
+    options: Uint8Array
+    composeMsg: Uint8Array | null
+    payInLzToken: boolean
+}>([...sendParamFields, ['payInLzToken', bool()]])
+
+const sendParamsSerializer = struct<{
+    dstEid: number
+    to: Uint8Array
+    amountLd: bigint
+    minAmountLd: bigint
+    options: Uint8Array
+    composeMsg: Uint8Array | null
+    nativeFee: bigint
+    lzTokenFee: bigint
+}>([...sendParamFields, ['nativeFee', u64()], ['lzTokenFee', u64()]])
+
+function discriminator(instruction: string): Uint8Array {
+    return createHash('sha256').update(`global:${instruction}`).digest().subarray(0, 8)
+}
+
+function instructionData(instruction: string, ...params: Uint8Array[]): Uint8Array {
+    return Buffer.concat([discriminator(instruction), ...params])
+}
+
+function readonly(pubkey: PublicKey): AccountMeta {
+    return { pubkey, isSigner: false, isWritable: false }
+}
+
+function writable(pubkey: PublicKey): AccountMeta {
+    return { pubkey, isSigner: false, isWritable: true }
+}
+
+function findPda(programId: PublicKey, seeds: Uint8Array[]): PublicKey {
+    const [pda] = Web3PublicKey.findProgramAddressSync(seeds, toWeb3JsPublicKey(programId))
+    return fromWeb3JsPublicKey(pda)
+}
+
+/**
+ * Returns the interface version reported by `oft_version`.
+ */
+export async function getOftInterfaceVersion(rpc: RpcInterface, programId: PublicKey, payer: PublicKey) {
+    const ix = { programId, keys: [], data: instructionData('oft_version') }
+    const version = await simulateWeb3JsTransaction(
+        rpc,
+        [toWeb3JsInstruction(ix)],
+        programId,
+        payer,
+        versionSerializer,
+        'confirmed'
+    )
+    return version.interface
+}
+
+async function getSendLibraryProgram(
+    rpc: RpcInterface,
+    endpoint: EndpointProgram.Endpoint,
+    payer: PublicKey,
+    oftStore: PublicKey,
+    dstEid: number
+): Promise<SimpleMessageLibProgram.SimpleMessageLib | UlnProgram.Uln> {
+    const sendLibInfo = await endpoint.getSendLibrary(rpc, oftStore, dstEid)
+    if (!sendLibInfo.programId) {
+        throw new Error('Send library not initialized or blocked message library')
+    }
+    const { programId: msgLibProgram } = sendLibInfo
+    const msgLibVersion = await endpoint.getMessageLibVersion(rpc, payer, msgLibProgram)
+    if (msgLibVersion.major === 0n && msgLibVersion.minor == 0 && msgLibVersion.endpointVersion == 2) {
+        return new SimpleMessageLibProgram.SimpleMessageLib(msgLibProgram)
+    } else if (msgLibVersion.major === 3n && msgLibVersion.minor == 0 && msgLibVersion.endpointVersion == 2) {
+        return new UlnProgram.Uln(msgLibProgram)
+    }
+    throw new Error(`Unsupported message library version: ${JSON.stringify(msgLibVersion, null, 2)}`)
+}
+
+/**
+ * Resolves the accounts shared by `quote_send` and `send`.
+ */
+async function getSendContext(
+    rpc: RpcInterface,
+    programId: PublicKey,
+    oftStore: PublicKey,
+    payer: PublicKey,
+    dstEid: number
+) {
+    const oftStoreInfo = await oft.accounts.fetchOFTStore({ rpc }, oftStore)
+    const [peer] = new OftPDA(programId).peer(oftStore, dstEid)
+    const peerInfo = await oft.accounts.fetchPeerConfig({ rpc }, peer)
+    const endpoint = new EndpointProgram.Endpoint(oftStoreInfo.endpointProgram)
+    const path: SolanaPacketPath = { dstEid, sender: oftStore, receiver: peerInfo.peerAddress }
+    const msgLibProgram = await getSendLibraryProgram(rpc, endpoint, payer, oftStore, dstEid)
+
+    // the subsidy vault is passed whenever it exists, so that quote_send and send agree on the fee
+    const subsidyVault = findPda(programId, [Buffer.from('SubsidyVault'), toWeb3JsPublicKey(oftStore).toBuffer()])
+    const hasSubsidyVault = await rpc.accountExists(subsidyVault)
+
+    return { peer, endpoint, path, msgLibProgram, subsidyVault: hasSubsidyVault ? subsidyVault : undefined }
+}
+
+/**
+ * Quotes the messaging fee of a send, after the fee subsidy of the store if any.
+ */
+export async function quoteSend(
+    rpc: RpcInterface,
+    accounts: { payer: PublicKey; oftStore: PublicKey; tokenMint: PublicKey },
+    params: SendParamArgs & { payInLzToken: boolean },
+    programId: PublicKey
+): Promise<{ nativeFee: bigint; lzTokenFee: bigint }> {
+    const { payer, oftStore, tokenMint } = accounts
+    const { peer, endpoint, path, msgLibProgram, subsidyVault } = await getSendContext(
+        rpc,
+        programId,
+        oftStore,
+        payer,
+        params.dstEid
+    )
+    const remainingAccounts = await endpoint.getQuoteIXAccountMetaForCPI(rpc, payer, { path, msgLibProgram })
+    const ix = {
+        programId,
+        keys: [
+            readonly(oftStore),
+            readonly(peer),
+            readonly(tokenMint),
+            readonly(subsidyVault ?? programId),
+            ...remainingAccounts,
+        ],
+        data: instructionData(
+            'quote_send',
+            quoteSendParamsSerializer.serialize({
+                ...params,
+                options: params.options ?? new Uint8Array(),
+                composeMsg: params.composeMsg ?? null,
+            }),
+            new Uint8Array([0]) // extensions: None
+        ),
+    }
+    return simulateWeb3JsTransaction(
+        rpc,
+        [ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), toWeb3JsInstruction(ix)],
+        programId,
+        payer,
+        messagingFeeSerializer,
+        'confirmed'
+    )
+}
+
+/**
+ * Builds the `send` instruction, without a max epoch, extensions or a referrer.
+ */
+export async function send(
+    rpc: RpcInterface,
+    accounts: {
+        payer: Signer
+        oftStore: PublicKey
+        tokenMint: PublicKey
+        tokenEscrow: PublicKey
+        tokenSource: PublicKey
+        tokenProgram: PublicKey
+    },
+    params: SendParamArgs & { nativeFee: bigint; lzTokenFee?: bigint },
+    programId: PublicKey
+): Promise<WrappedInstruction> {
+    const { payer, oftStore, tokenMint, tokenEscrow, tokenSource, tokenProgram } = accounts
+    const { peer, endpoint, path, msgLibProgram, subsidyVault } = await getSendContext(
+        rpc,
+        programId,
+        oftStore,
+        payer.publicKey,
+        params.dstEid
+    )
+    const remainingAccounts = await endpoint.getSendIXAccountMetaForCPI(rpc, payer.publicKey, { path, msgLibProgram })
+    if (remainingAccounts === undefined) {
+        throw new Error('Failed to get remaining accounts for send instruction')
+    }
+    const eventAuthority = findPda(programId, [Buffer.from('__event_authority')])
+    const keys: AccountMeta[] = [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        writable(peer),
+        writable(oftStore),
+        writable(tokenSource),
+        writable(tokenEscrow),
+        writable(tokenMint),
+        readonly(tokenProgram),
+        // the program id stands for an absent optional account
+        subsidyVault ? writable(subsidyVault) : readonly(programId),
+        readonly(subsidyVault ? SYSTEM_PROGRAM_ID : programId),
+        readonly(programId), // referrer
+        readonly(eventAuthority),
+        readonly(programId),
+        ...remainingAccounts,
+    ]
+    const data = instructionData(
+        'send',
+        sendParamsSerializer.serialize({
+            ...params,
+            options: params.options ?? new Uint8Array(),
+            composeMsg: params.composeMsg ?? null,
+            lzTokenFee: params.lzTokenFee ?? 0n,
+        }),
+        new Uint8Array([0, 0, 0]) // max_epoch, extensions and referrer: None
+    )
+    return { instruction: { programId, keys, data }, signers: [payer], bytesCreatedOnChain: 0 }
+}
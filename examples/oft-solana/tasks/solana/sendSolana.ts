@@ -13,6 +13,7 @@ import { oft } from '@layerzerolabs/oft-v2-solana-sdk'
 import { SendResult } from '../common/types'
 import { DebugLogger, KnownErrors, isEmptyOptionsSolana } from '../common/utils'
 
+import { OFT_STORE_V2_SIZE, getOftInterfaceVersion, quoteSend, send } from './oftClient'
 import { parseDecimalToUnits, silenceSolana429 } from './utils'
 
 import {
@@ -74,6 +75,15 @@ export async function sendSolana({
 
     // 3️⃣ Decide your store PDA (override or from your on‐disk deployment)
     const storePda = oftAddress ? publicKey(oftAddress) : publicKey(getSolanaDeployment(srcEid).oftStore)
+    // oft-v2-solana-sdk builds the quote_send and send instructions of interface 2 programs only
+    const isInterface3 = (await getOftInterfaceVersion(umi.rpc, programId, umiWalletSigner.publicKey)) >= 3n
+    const oftStoreAccount = await umi.rpc.getAccount(storePda)
+    if (isInterface3 && oftStoreAccount.exists && oftStoreAccount.data.length === OFT_STORE_V2_SIZE) {
+        throw new Error(
+            `OFT store ${storePda} has not been reallocated since the program was upgraded to interface 3. ` +
+                'See "Upgrading to program interface 3" in the README.'
+        )
+    }
     const oftStoreInfo = await oft.accounts.fetchOFTStore(umi, storePda)
     const mintPk = new PublicKey(oftStoreInfo.tokenMint)
     const escrowPk = new PublicKey(oftStoreInfo.tokenEscrow)
@@ -131,38 +141,69 @@ export async function sendSolana({
         options: extraOptions ? Buffer.from(extraOptions.replace(/^0x/, ''), 'hex') : undefined,
         composeMsg: composeMsg ? Buffer.from(composeMsg.replace(/^0x/, ''), 'hex') : undefined,
     }
-    const { nativeFee } = await oft.quote(
-        umi.rpc,
-        {
-            payer: umiWalletSigner.publicKey,
-            tokenMint: fromWeb3JsPublicKey(mintPk),
-            tokenEscrow: fromWeb3JsPublicKey(escrowPk),
-        },
-        {
-            payInLzToken: false,
-            ...sendParam,
-        },
-        { oft: programId }, // ← use override
-        [],
-        lookupTableAddresses
-    )
+    const { nativeFee } = isInterface3
+        ? await quoteSend(
+              umi.rpc,
+              {
+                  payer: umiWalletSigner.publicKey,
+                  oftStore: storePda,
+                  tokenMint: fromWeb3JsPublicKey(mintPk),
+              },
+              {
+                  payInLzToken: false,
+                  ...sendParam,
+              },
+              programId
+          )
+        : await oft.quote(
+              umi.rpc,
+              {
+                  payer: umiWalletSigner.publicKey,
+                  tokenMint: fromWeb3JsPublicKey(mintPk),
+                  tokenEscrow: fromWeb3JsPublicKey(escrowPk),
+              },
+              {
+                  payInLzToken: false,
+                  ...sendParam,
+              },
+              { oft: programId }, // ← use override
+              [],
+              lookupTableAddresses
+          )
 
     // 8️⃣ Send (again passing `programId`)
     logger.info('Sending the transaction...')
-    const ix = await oft.send(
-        umi.rpc,
-        {
-            payer: umiWalletSigner,
-            tokenMint: fromWeb3JsPublicKey(mintPk),
-            tokenEscrow: fromWeb3JsPublicKey(escrowPk),
-            tokenSource: tokenAccount[0],
-        },
-        {
-            nativeFee,
-            ...sendParam,
-        },
-        { oft: programId, token: tokenProgramId } // ← use override
-    )
+    const ix = isInterface3
+        ? await send(
+              umi.rpc,
+              {
+                  payer: umiWalletSigner,
+                  oftStore: storePda,
+                  tokenMint: fromWeb3JsPublicKey(mintPk),
+                  tokenEscrow: fromWeb3JsPublicKey(escrowPk),
+                  tokenSource: tokenAccount[0],
+                  tokenProgram: tokenProgramId,
+              },
+              {
+                  nativeFee,
+                  ...sendParam,
+              },
+              programId
+          )
+        : await oft.send(
+              umi.rpc,
+              {
+                  payer: umiWalletSigner,
+                  tokenMint: fromWeb3JsPublicKey(mintPk),
+                  tokenEscrow: fromWeb3JsPublicKey(escrowPk),
+                  tokenSource: tokenAccount[0],
+              },
+              {
+                  nativeFee,
+                  ...sendParam,
+              },
+              { oft: programId, token: tokenProgramId } // ← use override
+          )
 
     // 9️⃣ Compute units & submit
     let txB = transactionBuilder().add([ix])
@@ -1,4 +1,11 @@
-import { Umi, publicKey } from '@metaplex-foundation/umi'
+import {
+    TransactionBuilder,
+    Umi,
+    PublicKey as UmiPublicKey,
+    WrappedInstruction,
+    publicKey,
+    transactionBuilder,
+} from '@metaplex-foundation/umi'
 import { toWeb3JsPublicKey } from '@metaplex-foundation/umi-web3js-adapters'
 import { Connection, PublicKey } from '@solana/web3.js'
 import * as multisig from '@sqds/multisig'
@@ -89,6 +96,40 @@ export function silenceSolana429(connection: Connection): void {
     }) as typeof process.stderr.write
 }
 
+// `init_oft` reads its optional `mint_authority` account right after `system_program`, which
+// @layerzerolabs/oft-v2-solana-sdk does not pass.
+const INIT_OFT_MINT_AUTHORITY_INDEX = 7
+
+/**
+ * Inserts the `mint_authority` account into the `init_oft` instruction built by the SDK.
+ * @param initOft - The result of `oft.initOft`
+ * @param programId - The OFT program ID, which also stands for an absent optional account
+ * @param mintAuthority - The mint authority of a Native OFT, i.e. the OFT Store or the Mint Authority Multisig
+ * @returns A transaction builder with the completed `init_oft` instruction
+ */
+export function withInitOftMintAuthority(
+    initOft: TransactionBuilder | WrappedInstruction,
+    programId: UmiPublicKey,
+    mintAuthority?: UmiPublicKey
+): TransactionBuilder {
+    return transactionBuilder(
+        transactionBuilder()
+            .add(initOft)
+            .items.map((item) => {
+                if (item.instruction.programId !== programId) {
+                    return item
+                }
+                const keys = [...item.instruction.keys]
+                keys.splice(INIT_OFT_MINT_AUTHORITY_INDEX, 0, {
+                    pubkey: mintAuthority ?? programId,
+                    isSigner: false,
+                    isWritable: false,
+                })
+                return { ...item, instruction: { ...item.instruction, keys } }
+            })
+    )
+}
+
 /**
  * Fetches the admin and delegate for a given OFT Store.
  * @param umi - The Umi instance